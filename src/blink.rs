@@ -0,0 +1,154 @@
+use {EightSegment, Error, SegmentPins, Segments};
+
+/// Blinks some or all of the segments of an [`EightSegment`] without blocking, for alarms and
+/// other states that need attention.
+///
+/// Call [`tick`](Blink::tick) regularly with a millisecond timestamp. The blinking segments are
+/// lit for [`on_ms`](Blink::on_ms), then dark for [`off_ms`](Blink::off_ms). Whatever was
+/// written to the display is left alone, so its [`current`](EightSegment::current) digit can
+/// still be changed through [`display_mut`](Blink::display_mut) while it blinks.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::{Blink, EightSegment, Segments};
+///
+///    let mut blink = Blink::new(EightSegment::new(pins, true), 500, 500);
+///    blink.display_mut().display(0xE, true).unwrap();
+///    // Only flash the decimal point, leaving the digit steady.
+///    blink.segments = Segments::P;
+///
+///    // then in the main loop
+///    blink.tick(millis()).unwrap();
+///```
+pub struct Blink<PINS, PWM = ()> {
+    /// The segments that blink. All of them by default.
+    pub segments: Segments,
+    /// How long the blinking segments stay lit, in milliseconds.
+    pub on_ms: u32,
+    /// How long the blinking segments stay dark, in milliseconds.
+    pub off_ms: u32,
+    display: EightSegment<PINS, PWM>,
+    running: bool,
+    /// When the current on/off cycle started, or `None` until the first tick.
+    start_ms: Option<u32>,
+}
+
+impl<PINS: SegmentPins, PWM> Blink<PINS, PWM> {
+    /// Wraps `display` and starts blinking the whole of it. Nothing changes on the pins until the
+    /// first [`tick`](Blink::tick), which begins with the segments lit.
+    pub fn new(display: EightSegment<PINS, PWM>, on_ms: u32, off_ms: u32) -> Self {
+        Blink {
+            segments: Segments::ALL,
+            on_ms,
+            off_ms,
+            display,
+            running: true,
+            start_ms: None,
+        }
+    }
+
+    /// Hands back the display. Any segments dark for the off phase light again on its next
+    /// write.
+    pub fn release(mut self) -> EightSegment<PINS, PWM> {
+        self.display.hidden = Segments::NONE;
+        self.display
+    }
+
+    pub fn display(&self) -> &EightSegment<PINS, PWM> {
+        &self.display
+    }
+
+    /// The wrapped display, for changing what it shows.
+    pub fn display_mut(&mut self) -> &mut EightSegment<PINS, PWM> {
+        &mut self.display
+    }
+
+    pub fn is_blinking(&self) -> bool {
+        self.running
+    }
+
+    /// Starts blinking again. The next [`tick`](Blink::tick) begins a fresh cycle, lit.
+    pub fn start(&mut self) {
+        self.running = true;
+        self.start_ms = None;
+    }
+
+    /// Stops blinking and lights the blinking segments steadily.
+    pub fn stop(&mut self) -> Result<(), Error<PINS::Error>> {
+        self.running = false;
+        self.hide(Segments::NONE)
+    }
+
+    /// Lights or darkens the blinking segments for the time `now_ms`. The timestamp may wrap
+    /// around.
+    pub fn tick(&mut self, now_ms: u32) -> Result<(), Error<PINS::Error>> {
+        if !self.running {
+            return Ok(());
+        }
+        let start = *self.start_ms.get_or_insert(now_ms);
+        let period = self.on_ms.saturating_add(self.off_ms);
+        let hidden = if period != 0 && now_ms.wrapping_sub(start) % period >= self.on_ms {
+            self.segments
+        } else {
+            Segments::NONE
+        };
+        self.hide(hidden)
+    }
+
+    fn hide(&mut self, hidden: Segments) -> Result<(), Error<PINS::Error>> {
+        if hidden == self.display.hidden {
+            return Ok(());
+        }
+        self.display.hidden = hidden;
+        self.display.drive()
+    }
+}
+
+/// Pulses the decimal point of an [`EightSegment`] as a heartbeat, independently of the digit
+/// shown.
+///
+/// Call [`tick`](Heartbeat::tick) regularly with a millisecond timestamp. The dot is lit for the
+/// first half of every [`period_ms`](Heartbeat::period_ms) and dark for the second.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::{EightSegment, Heartbeat};
+///
+///    let mut display = EightSegment::new(pins, true);
+///    let mut heartbeat = Heartbeat::new(1000);
+///    display.display(0x3, false).unwrap();
+///
+///    // then in the main loop
+///    heartbeat.tick(&mut display, millis()).unwrap();
+///```
+pub struct Heartbeat {
+    /// How long one on/off pulse of the dot takes, in milliseconds.
+    pub period_ms: u32,
+    /// When the first pulse started, or `None` until the first tick.
+    start_ms: Option<u32>,
+}
+
+impl Heartbeat {
+    pub fn new(period_ms: u32) -> Self {
+        Heartbeat {
+            period_ms,
+            start_ms: None,
+        }
+    }
+
+    /// Lights or darkens the decimal point of `display` for the time `now_ms`. The pins are
+    /// only touched when the dot changes. The timestamp may wrap around.
+    pub fn tick<PINS: SegmentPins, PWM>(
+        &mut self,
+        display: &mut EightSegment<PINS, PWM>,
+        now_ms: u32,
+    ) -> Result<(), Error<PINS::Error>> {
+        let start = *self.start_ms.get_or_insert(now_ms);
+        let on =
+            self.period_ms == 0 || now_ms.wrapping_sub(start) % self.period_ms < self.period_ms / 2;
+        if display.current().contains(Segments::P) == on {
+            return Ok(());
+        }
+        display.set_dp(on)
+    }
+}
@@ -0,0 +1,245 @@
+use core::fmt;
+
+use {font, Segments};
+
+/// The segments for up to `N` digits, built up by formatting text into it with `write!`.
+///
+/// Characters are looked up in [`font`]. A `'.'` lights the decimal point of the digit before
+/// it rather than taking a digit of its own, unless that digit's point is already lit or there
+/// is no digit before it. Writing more than `N` digits, or a character with no glyph, makes the
+/// write fail; [`overflowed`](DigitBuffer::overflowed) tells the two apart.
+///
+/// # Examples
+///```rust,ignore
+///    use core::fmt::Write;
+///    use eight_segment::{DigitBuffer, DigitDisplay};
+///
+///    let mut buffer = DigitBuffer::<4>::new();
+///    // "21.5" fits in three digits, so it lands on the right of the four.
+///    write!(buffer, "{:.1}", temperature).unwrap();
+///    display.write_digits(&buffer.right_aligned()).unwrap();
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitBuffer<const N: usize> {
+    digits: [Segments; N],
+    len: usize,
+    overflowed: bool,
+}
+
+impl<const N: usize> DigitBuffer<N> {
+    pub const fn new() -> Self {
+        DigitBuffer {
+            digits: [Segments::NONE; N],
+            len: 0,
+            overflowed: false,
+        }
+    }
+
+    /// Empties the buffer, ready for the next value.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// How many digits have been written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether a write was cut short because the text needed more than `N` digits. The digits
+    /// that fitted are kept.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// The digits written so far, leftmost first.
+    pub fn digits(&self) -> &[Segments] {
+        &self.digits[..self.len]
+    }
+
+    /// All `N` digits with the ones written moved to the right and blanks on the left, the way
+    /// numbers are usually shown.
+    pub fn right_aligned(&self) -> [Segments; N] {
+        let mut digits = [Segments::NONE; N];
+        digits[N - self.len..].copy_from_slice(self.digits());
+        digits
+    }
+
+    /// Fills all `N` digits with `value` in `radix`, right-aligned, either padded with zeros or
+    /// with the unused digits blank.
+    ///
+    /// If it doesn't fit, every digit shows a dash instead and
+    /// [`overflowed`](DigitBuffer::overflowed) is set.
+    pub fn set_u32(&mut self, value: u32, radix: Radix, leading_zeros: bool) {
+        self.set_number(false, value, radix, 0, leading_zeros);
+    }
+
+    /// Like [`set_u32`](DigitBuffer::set_u32), with a negative `value` shown behind a minus
+    /// sign on the `g` segment. With leading zeros, the minus sign takes the leftmost digit.
+    pub fn set_i32(&mut self, value: i32, radix: Radix, leading_zeros: bool) {
+        self.set_number(value < 0, value.unsigned_abs(), radix, 0, leading_zeros);
+    }
+
+    /// Fills all `N` digits with `value` divided by 10 to the power `decimals`, right-aligned,
+    /// so 2345 with 2 decimals shows as `23.45` and 5 with 2 decimals as `0.05`.
+    ///
+    /// If it doesn't fit, every digit shows a dash instead and
+    /// [`overflowed`](DigitBuffer::overflowed) is set.
+    pub fn set_fixed(&mut self, value: i32, decimals: u8) {
+        self.set_number(
+            value < 0,
+            value.unsigned_abs(),
+            Radix::Decimal,
+            decimals,
+            false,
+        );
+    }
+
+    /// Fills all `N` digits with `value` rounded to as many decimal places as fit, up to
+    /// `precision`, so 1.23456 with a precision of 3 shows as `1.235` on four digits and `1.23`
+    /// on three.
+    ///
+    /// If even the whole number doesn't fit, or `value` is infinite or NaN, every digit shows a
+    /// dash instead and [`overflowed`](DigitBuffer::overflowed) is set.
+    #[cfg(feature = "float")]
+    pub fn set_f32(&mut self, value: f32, precision: u8) {
+        if value.is_finite() {
+            // More decimals than digits can never fit, and would overflow `scale` to infinity.
+            let precision = precision.min(N.saturating_sub(1).min(usize::from(u8::MAX)) as u8);
+            let mut scale = 1.0;
+            for _ in 0..precision {
+                scale *= 10.0;
+            }
+            for decimals in (0..=precision).rev() {
+                let scaled = value * scale;
+                if scaled.abs() < 2_147_483_648.0 {
+                    // Round half away from zero, as `f32::round` isn't available without std.
+                    // Adding 0.5 would itself round, taking 0.49999997 up to 1, but the fraction
+                    // left after truncating is exact.
+                    let truncated = scaled as i32;
+                    let fraction = scaled - truncated as f32;
+                    let fixed = if fraction >= 0.5 {
+                        truncated + 1
+                    } else if fraction <= -0.5 {
+                        truncated - 1
+                    } else {
+                        truncated
+                    };
+                    if width(fixed, decimals) <= N {
+                        return self.set_fixed(fixed, decimals);
+                    }
+                }
+                scale /= 10.0;
+            }
+        }
+        self.clear();
+        self.set_overflow();
+    }
+
+    fn set_number(
+        &mut self,
+        negative: bool,
+        mut magnitude: u32,
+        radix: Radix,
+        decimals: u8,
+        leading_zeros: bool,
+    ) {
+        self.clear();
+        self.len = N;
+        let base = radix as u32;
+        // Digits are filled in from the right, so `start` is the leftmost one used so far.
+        let mut start = N;
+        loop {
+            if start == 0 {
+                return self.set_overflow();
+            }
+            start -= 1;
+            self.digits[start] = Segments::hex((magnitude % base) as u8).unwrap_or(Segments::NONE);
+            magnitude /= base;
+            if magnitude == 0 && N - start > usize::from(decimals) {
+                break;
+            }
+        }
+        if decimals > 0 {
+            self.digits[N - 1 - usize::from(decimals)] |= Segments::P;
+        }
+        if negative {
+            if start == 0 {
+                return self.set_overflow();
+            }
+            start = if leading_zeros { 0 } else { start - 1 };
+            self.digits[start] = Segments::G;
+        }
+        if leading_zeros {
+            for digit in &mut self.digits[usize::from(negative)..] {
+                if digit.is_empty() {
+                    *digit = Segments::hex(0).unwrap_or(Segments::NONE);
+                }
+            }
+        }
+    }
+
+    fn set_overflow(&mut self) {
+        self.digits = [Segments::G; N];
+        self.len = N;
+        self.overflowed = true;
+    }
+
+    /// Appends a digit, or returns `false` and marks the buffer as overflowed if it's full.
+    fn push(&mut self, segments: Segments) -> bool {
+        if self.len == N {
+            self.overflowed = true;
+            return false;
+        }
+        self.digits[self.len] = segments;
+        self.len += 1;
+        true
+    }
+}
+
+/// How many digits [`DigitBuffer::set_fixed`] needs to show `value` with `decimals` places.
+#[cfg(feature = "float")]
+fn width(value: i32, decimals: u8) -> usize {
+    let mut magnitude = value.unsigned_abs();
+    let mut width = 1;
+    while magnitude >= 10 {
+        magnitude /= 10;
+        width += 1;
+    }
+    width.max(usize::from(decimals) + 1) + usize::from(value < 0)
+}
+
+/// The base a number is shown in by [`DigitBuffer::set_u32`] and [`DigitBuffer::set_i32`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Radix {
+    Binary = 2,
+    Octal = 8,
+    #[default]
+    Decimal = 10,
+    Hexadecimal = 16,
+}
+
+impl<const N: usize> Default for DigitBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for DigitBuffer<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c == '.' && self.len > 0 && !self.digits[self.len - 1].contains(Segments::P) {
+                self.digits[self.len - 1] |= Segments::P;
+                continue;
+            }
+            let segments = font::glyph(c).ok_or(fmt::Error)?;
+            if !self.push(segments) {
+                return Err(fmt::Error);
+            }
+        }
+        Ok(())
+    }
+}
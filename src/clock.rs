@@ -0,0 +1,112 @@
+use Segments;
+
+/// Which two fields of the time a [`ClockFormat`] shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClockFields {
+    /// `HH:MM`. The default.
+    #[default]
+    HoursMinutes,
+    /// `MM:SS`, for timers.
+    MinutesSeconds,
+}
+
+/// Where a [`ClockFormat`] puts the colon between the two fields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColonStyle {
+    /// Light `p` on the second digit. On modules like the TM1637 clock boards this is wired to
+    /// the colon; on plain displays it's the decimal point between the fields. The default.
+    #[default]
+    DecimalPoint,
+    /// Leave the digits alone and only report it in [`ClockDigits::colon`], for displays with a
+    /// separate colon like the HT16K33 backpacks.
+    Separate,
+}
+
+/// Turns a time of day into four digits for a clock display.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::{ClockFormat, DigitDisplay};
+///
+///    let format = ClockFormat {
+///        twelve_hour: true,
+///        leading_zero: false,
+///        ..ClockFormat::default()
+///    };
+///    // Shows " 1:05" with the colon lit and the PM dot on the last digit.
+///    let clock = format.render(13, 5, 42);
+///    display.write_digits(&clock.digits).unwrap();
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockFormat {
+    pub fields: ClockFields,
+    pub colon: ColonStyle,
+    /// Whether the colon is only lit on even seconds, so it blinks once a second.
+    pub blink_colon: bool,
+    /// Whether hours run from 1 to 12, with the last digit's `p` lit after noon.
+    pub twelve_hour: bool,
+    /// Whether the first field is padded with a zero, as in `09:30`, or left blank, as in
+    /// ` 9:30`.
+    pub leading_zero: bool,
+}
+
+/// Four digits rendered by [`ClockFormat::render`], leftmost first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockDigits {
+    pub digits: [Segments; 4],
+    /// Whether the colon should be lit. With [`ColonStyle::DecimalPoint`] it's already in
+    /// `digits`.
+    pub colon: bool,
+    /// Whether it's after noon. In twelve hour mode this is already in `digits`.
+    pub pm: bool,
+}
+
+impl Default for ClockFormat {
+    fn default() -> Self {
+        ClockFormat {
+            fields: ClockFields::default(),
+            colon: ColonStyle::default(),
+            blink_colon: true,
+            twelve_hour: false,
+            leading_zero: true,
+        }
+    }
+}
+
+impl ClockFormat {
+    /// Renders the time. Out of range values wrap, so 25 hours shows as 01.
+    pub fn render(&self, hours: u8, minutes: u8, seconds: u8) -> ClockDigits {
+        let hours = hours % 24;
+        let pm = hours >= 12;
+        let (first, second) = match self.fields {
+            ClockFields::HoursMinutes if self.twelve_hour => match hours % 12 {
+                0 => (12, minutes % 60),
+                hours => (hours, minutes % 60),
+            },
+            ClockFields::HoursMinutes => (hours, minutes % 60),
+            ClockFields::MinutesSeconds => (minutes % 60, seconds % 60),
+        };
+        let colon = !self.blink_colon || seconds.is_multiple_of(2);
+
+        let mut digits = [
+            decimal(first / 10),
+            decimal(first % 10),
+            decimal(second / 10),
+            decimal(second % 10),
+        ];
+        if first < 10 && !self.leading_zero {
+            digits[0] = Segments::NONE;
+        }
+        if self.colon == ColonStyle::DecimalPoint {
+            digits[1].set(Segments::P, colon);
+        }
+        if self.twelve_hour && self.fields == ClockFields::HoursMinutes {
+            digits[3].set(Segments::P, pm);
+        }
+        ClockDigits { digits, colon, pm }
+    }
+}
+
+fn decimal(digit: u8) -> Segments {
+    Segments::hex(digit).unwrap_or(Segments::G)
+}
@@ -0,0 +1,65 @@
+//! Adapters for HALs that still implement the embedded-hal 0.2 traits.
+//!
+//! Wrap each 0.2 pin in an [`Eh02Pin`] before handing the pins to [`EightSegment::new`]:
+//!```rust,ignore
+//!    use eight_segment::eh02::Eh02Pin;
+//!
+//!    let mut eight_segment = EightSegment::new(
+//!        [seg_a, seg_b, seg_c, seg_d, seg_e, seg_f, seg_g, seg_p].map(Eh02Pin::new),
+//!        false,
+//!    );
+//!```
+//!
+//! [`EightSegment::new`]: crate::EightSegment::new
+
+use core::fmt::Debug;
+
+use embedded_hal::digital::{self, ErrorKind, ErrorType, OutputPin};
+use embedded_hal_02::digital::v2::OutputPin as Eh02OutputPin;
+
+/// An embedded-hal 0.2 output pin usable wherever an embedded-hal 1.0 `OutputPin` is expected.
+#[derive(Debug)]
+pub struct Eh02Pin<P>(P);
+
+impl<P> Eh02Pin<P> {
+    pub fn new(pin: P) -> Self {
+        Eh02Pin(pin)
+    }
+
+    /// Hands back the wrapped pin.
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+/// The error of a wrapped embedded-hal 0.2 pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eh02Error<E>(pub E);
+
+impl<E: Debug> digital::Error for Eh02Error<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<P> ErrorType for Eh02Pin<P>
+where
+    P: Eh02OutputPin,
+    P::Error: Debug,
+{
+    type Error = Eh02Error<P::Error>;
+}
+
+impl<P> OutputPin for Eh02Pin<P>
+where
+    P: Eh02OutputPin,
+    P::Error: Debug,
+{
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low().map_err(Eh02Error)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high().map_err(Eh02Error)
+    }
+}
@@ -0,0 +1,26 @@
+/// Errors returned by the display drivers in this crate.
+///
+/// `E` is the error type of the underlying pins or bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<E> {
+    /// Setting the state of a segment pin failed.
+    Pin(E),
+    /// A transfer to a display controller over SPI or I2C failed.
+    Bus(E),
+    /// Setting the duty cycle of the PWM output on the common line failed.
+    Pwm(E),
+    /// The value can't be shown on a single digit.
+    InvalidDigit(u8),
+    /// The character has no seven-segment glyph in [`font`](crate::font).
+    UnsupportedChar(char),
+    /// There is no digit at this position on the display.
+    InvalidPosition(usize),
+    /// A display controller didn't acknowledge a byte, so it is probably missing or miswired.
+    NoAcknowledge,
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(error: E) -> Self {
+        Error::Pin(error)
+    }
+}
@@ -0,0 +1,162 @@
+//! Segment pins on an I2C GPIO expander, so a single-digit display needs only the two I2C pins.
+//!
+//! Both expanders implement [`SegmentPins`], so they go into an [`EightSegment`] in place of
+//! the segment pins. Every write sends all eight segments in one I2C transaction, and polarity is
+//! still set with [`EightSegment::high_on`]. I2C errors come back as [`Error::Pin`], including
+//! from [`Mcp23017::init`], since the bus stands in for the segment pins.
+//!
+//! [`EightSegment`]: crate::EightSegment
+//! [`EightSegment::high_on`]: crate::EightSegment::high_on
+//! [`Error::Pin`]: crate::Error::Pin
+
+use embedded_hal::digital::PinState;
+use embedded_hal::i2c::I2c;
+
+use {Error, Segment, SegmentMap, SegmentPins, Segments};
+
+const MCP23017_IODIRA: u8 = 0x00;
+const MCP23017_OLATA: u8 = 0x14;
+
+/// A PCF8574 or PCF8574A with the segments on P0 to P7.
+///
+/// Its outputs can sink far more current than they source, so wire the segments to light when
+/// pulled low and set `high_on` to `false`.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::expander::Pcf8574;
+///    use eight_segment::{EightSegment, SegmentMap};
+///
+///    let pins = Pcf8574::new(i2c, 0x20, SegmentMap::IDENTITY);
+///    let mut eight_segment = EightSegment::new(pins, false);
+///    eight_segment.display(0x4, false).unwrap();
+///```
+pub struct Pcf8574<I2C> {
+    i2c: I2C,
+    address: u8,
+    map: SegmentMap,
+    levels: u8,
+}
+
+impl<I2C: I2c> Pcf8574<I2C> {
+    /// Takes the I2C bus and the expander's 7-bit address, 0x20 to 0x27 for the PCF8574 or 0x38
+    /// to 0x3F for the PCF8574A. `map` says which of P0 to P7 each segment is wired to.
+    pub fn new(i2c: I2C, address: u8, map: SegmentMap) -> Self {
+        Pcf8574 {
+            i2c,
+            address,
+            map,
+            levels: 0xFF,
+        }
+    }
+
+    /// Hands back the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C: I2c> SegmentPins for Pcf8574<I2C> {
+    type Error = I2C::Error;
+
+    fn set_pin(&mut self, segment: Segment, state: PinState) -> Result<(), Self::Error> {
+        let bit = Segments::from(segment).bits();
+        let level = if state == PinState::High { bit } else { 0 };
+        self.set_pins(level, bit)
+    }
+
+    /// Writes all eight pins in one transaction, keeping the unmasked ones as they were.
+    fn set_pins(&mut self, levels: u8, mask: u8) -> Result<(), Self::Error> {
+        let levels = self.levels & !mask | levels & mask;
+        self.i2c
+            .write(self.address, &[self.map.to_bits(levels.into())])?;
+        self.levels = levels;
+        Ok(())
+    }
+}
+
+/// One of the MCP23017's two 8-bit ports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Port {
+    A,
+    B,
+}
+
+/// One port of an MCP23017 with the segments on GP0 to GP7, leaving the other port free.
+///
+/// Call [`init`](Mcp23017::init) to make the port an output before the first write. The other
+/// port isn't touched.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::expander::{Mcp23017, Port};
+///    use eight_segment::{EightSegment, SegmentMap};
+///
+///    let mut pins = Mcp23017::new(i2c, 0x20, Port::A, SegmentMap::IDENTITY);
+///    pins.init().unwrap();
+///    let mut eight_segment = EightSegment::new(pins, true);
+///    eight_segment.display(0x4, false).unwrap();
+///```
+pub struct Mcp23017<I2C> {
+    i2c: I2C,
+    address: u8,
+    port: Port,
+    map: SegmentMap,
+    levels: u8,
+}
+
+impl<I2C: I2c> Mcp23017<I2C> {
+    /// Takes the I2C bus, the expander's 7-bit address from 0x20 to 0x27, and the port the
+    /// segments are on. `map` says which of GP0 to GP7 each segment is wired to.
+    pub fn new(i2c: I2C, address: u8, port: Port, map: SegmentMap) -> Self {
+        Mcp23017 {
+            i2c,
+            address,
+            port,
+            map,
+            levels: 0,
+        }
+    }
+
+    /// Hands back the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Makes every pin of the port an output. Assumes the expander's registers are in their
+    /// power-on layout, with IOCON.BANK clear.
+    pub fn init(&mut self) -> Result<(), Error<I2C::Error>> {
+        let register = self.register(MCP23017_IODIRA);
+        self.i2c
+            .write(self.address, &[register, 0x00])
+            .map_err(Error::Pin)
+    }
+
+    /// The address of `register` for this port. Port B's registers follow port A's.
+    fn register(&self, register: u8) -> u8 {
+        match self.port {
+            Port::A => register,
+            Port::B => register + 1,
+        }
+    }
+}
+
+impl<I2C: I2c> SegmentPins for Mcp23017<I2C> {
+    type Error = I2C::Error;
+
+    fn set_pin(&mut self, segment: Segment, state: PinState) -> Result<(), Self::Error> {
+        let bit = Segments::from(segment).bits();
+        let level = if state == PinState::High { bit } else { 0 };
+        self.set_pins(level, bit)
+    }
+
+    /// Writes all eight pins in one transaction, keeping the unmasked ones as they were.
+    fn set_pins(&mut self, levels: u8, mask: u8) -> Result<(), Self::Error> {
+        let levels = self.levels & !mask | levels & mask;
+        let register = self.register(MCP23017_OLATA);
+        self.i2c
+            .write(self.address, &[register, self.map.to_bits(levels.into())])?;
+        self.levels = levels;
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use segments::Segments;
+
+/// The seven-segment glyphs for the printable ASCII characters from `' '` (0x20) to `'~'` (0x7E).
+///
+/// Letters that only have a sensible upper or lower case shape, like `b` or `d`, use it for
+/// both cases. Characters that can't be drawn recognisably, such as `K`, `M`, `W` and `X`, are
+/// `None`. The decimal point is its own glyph so a lone `'.'` lights only `p`.
+pub const ASCII: [Option<Segments>; 95] = [
+    Some(Segments::from_bits(0x00)), // ' '
+    None,                            // '!'
+    Some(Segments::from_bits(0x22)), // '"'
+    None,                            // '#'
+    None,                            // '$'
+    None,                            // '%'
+    None,                            // '&'
+    Some(Segments::from_bits(0x02)), // '\''
+    Some(Segments::from_bits(0x39)), // '('
+    Some(Segments::from_bits(0x0F)), // ')'
+    None,                            // '*'
+    None,                            // '+'
+    None,                            // ','
+    Some(Segments::from_bits(0x40)), // '-'
+    Some(Segments::from_bits(0x80)), // '.'
+    Some(Segments::from_bits(0x52)), // '/'
+    Some(Segments::from_bits(0x3F)), // '0'
+    Some(Segments::from_bits(0x06)), // '1'
+    Some(Segments::from_bits(0x5B)), // '2'
+    Some(Segments::from_bits(0x4F)), // '3'
+    Some(Segments::from_bits(0x66)), // '4'
+    Some(Segments::from_bits(0x6D)), // '5'
+    Some(Segments::from_bits(0x7D)), // '6'
+    Some(Segments::from_bits(0x07)), // '7'
+    Some(Segments::from_bits(0x7F)), // '8'
+    Some(Segments::from_bits(0x67)), // '9'
+    None,                            // ':'
+    None,                            // ';'
+    None,                            // '<'
+    Some(Segments::from_bits(0x48)), // '='
+    None,                            // '>'
+    Some(Segments::from_bits(0x53)), // '?'
+    None,                            // '@'
+    Some(Segments::from_bits(0x77)), // 'A'
+    Some(Segments::from_bits(0x7C)), // 'B'
+    Some(Segments::from_bits(0x39)), // 'C'
+    Some(Segments::from_bits(0x5E)), // 'D'
+    Some(Segments::from_bits(0x79)), // 'E'
+    Some(Segments::from_bits(0x71)), // 'F'
+    Some(Segments::from_bits(0x3D)), // 'G'
+    Some(Segments::from_bits(0x76)), // 'H'
+    Some(Segments::from_bits(0x30)), // 'I'
+    Some(Segments::from_bits(0x1E)), // 'J'
+    None,                            // 'K'
+    Some(Segments::from_bits(0x38)), // 'L'
+    None,                            // 'M'
+    Some(Segments::from_bits(0x37)), // 'N'
+    Some(Segments::from_bits(0x3F)), // 'O'
+    Some(Segments::from_bits(0x73)), // 'P'
+    Some(Segments::from_bits(0x67)), // 'Q'
+    Some(Segments::from_bits(0x50)), // 'R'
+    Some(Segments::from_bits(0x6D)), // 'S'
+    Some(Segments::from_bits(0x78)), // 'T'
+    Some(Segments::from_bits(0x3E)), // 'U'
+    None,                            // 'V'
+    None,                            // 'W'
+    None,                            // 'X'
+    Some(Segments::from_bits(0x6E)), // 'Y'
+    Some(Segments::from_bits(0x5B)), // 'Z'
+    Some(Segments::from_bits(0x39)), // '['
+    Some(Segments::from_bits(0x64)), // '\\'
+    Some(Segments::from_bits(0x0F)), // ']'
+    Some(Segments::from_bits(0x23)), // '^'
+    Some(Segments::from_bits(0x08)), // '_'
+    Some(Segments::from_bits(0x20)), // '`'
+    Some(Segments::from_bits(0x5F)), // 'a'
+    Some(Segments::from_bits(0x7C)), // 'b'
+    Some(Segments::from_bits(0x58)), // 'c'
+    Some(Segments::from_bits(0x5E)), // 'd'
+    Some(Segments::from_bits(0x7B)), // 'e'
+    Some(Segments::from_bits(0x71)), // 'f'
+    Some(Segments::from_bits(0x6F)), // 'g'
+    Some(Segments::from_bits(0x74)), // 'h'
+    Some(Segments::from_bits(0x10)), // 'i'
+    Some(Segments::from_bits(0x0E)), // 'j'
+    None,                            // 'k'
+    Some(Segments::from_bits(0x30)), // 'l'
+    None,                            // 'm'
+    Some(Segments::from_bits(0x54)), // 'n'
+    Some(Segments::from_bits(0x5C)), // 'o'
+    Some(Segments::from_bits(0x73)), // 'p'
+    Some(Segments::from_bits(0x67)), // 'q'
+    Some(Segments::from_bits(0x50)), // 'r'
+    Some(Segments::from_bits(0x6D)), // 's'
+    Some(Segments::from_bits(0x78)), // 't'
+    Some(Segments::from_bits(0x1C)), // 'u'
+    None,                            // 'v'
+    None,                            // 'w'
+    None,                            // 'x'
+    Some(Segments::from_bits(0x6E)), // 'y'
+    Some(Segments::from_bits(0x5B)), // 'z'
+    None,                            // '{'
+    Some(Segments::from_bits(0x30)), // '|'
+    None,                            // '}'
+    None,                            // '~'
+];
+
+/// The degree sign, which isn't ASCII but comes up on every thermometer.
+pub const DEGREE: Segments = Segments::from_bits(0x63);
+
+/// Looks up the glyph for `c`, or `None` if it can't be drawn on seven segments.
+pub fn glyph(c: char) -> Option<Segments> {
+    match c {
+        '\u{b0}' => Some(DEGREE),
+        ' '..='~' => ASCII[c as usize - ' ' as usize],
+        _ => None,
+    }
+}
@@ -0,0 +1,216 @@
+//! Driver for the HT16K33 on the Adafruit-style 4-digit seven-segment I2C backpacks.
+
+use embedded_hal::i2c::I2c;
+
+use {DigitDisplay, Error, Segments};
+
+/// The address with none of the A0 to A2 jumpers bridged.
+pub const DEFAULT_ADDRESS: u8 = 0x70;
+
+const SYSTEM_SETUP: u8 = 0x20;
+const OSCILLATOR_ON: u8 = 0x01;
+const DISPLAY_SETUP: u8 = 0x80;
+const DISPLAY_ON: u8 = 0x01;
+const DIMMING: u8 = 0xE0;
+
+/// The display RAM address of each digit. The colon and other indicators sit in between, at
+/// [`INDICATORS`].
+const DIGITS: [u8; 4] = [0x00, 0x02, 0x06, 0x08];
+const INDICATORS: u8 = 0x04;
+
+/// How fast the whole display blinks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlinkRate {
+    #[default]
+    Off,
+    TwoHz,
+    OneHz,
+    HalfHz,
+}
+
+/// The segments on the backpack that aren't part of a digit.
+///
+/// The bit for each one in RAM address 0x04 follows the comments on `writeColon` in Adafruit's
+/// LED Backpack library and the 1.2" 7-segment backpack guide: 0x02 for the colon, 0x04 for the
+/// left colon's lower dot, 0x08 for its upper dot and 0x10 for the decimal point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indicator {
+    /// The colon between digits 1 and 2.
+    Colon,
+    /// The upper dot of the colon left of digit 0, on the 1.2" displays.
+    LeftColonUpper,
+    /// The lower dot of the colon left of digit 0, on the 1.2" displays.
+    LeftColonLower,
+    /// The dot above and left of digit 2, on the 1.2" displays.
+    DecimalPoint,
+}
+
+impl Indicator {
+    fn bit(self) -> u8 {
+        match self {
+            Indicator::Colon => 0x02,
+            Indicator::LeftColonUpper => 0x08,
+            Indicator::LeftColonLower => 0x04,
+            Indicator::DecimalPoint => 0x10,
+        }
+    }
+}
+
+/// A 4-digit display behind an HT16K33.
+///
+/// The colon has a RAM address of its own between digits 1 and 2, which this driver hides; use
+/// [`set_colon`](Ht16k33::set_colon) for it.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::ht16k33::{BlinkRate, Ht16k33, DEFAULT_ADDRESS};
+///    use eight_segment::{DigitDisplay, Segments};
+///
+///    let mut display = Ht16k33::new(i2c, DEFAULT_ADDRESS);
+///    display.init().unwrap();
+///    display.set_dimming(8).unwrap();
+///    display.write_digits(&[Segments::hex(1).unwrap(), Segments::hex(2).unwrap()]).unwrap();
+///    display.set_colon(true).unwrap();
+///    display.set_blink_rate(BlinkRate::OneHz).unwrap();
+///```
+pub struct Ht16k33<I2C> {
+    i2c: I2C,
+    address: u8,
+    frame: [Segments; 4],
+    indicators: u8,
+    blink_rate: BlinkRate,
+    on: bool,
+}
+
+impl<I2C> Ht16k33<I2C>
+where
+    I2C: I2c,
+{
+    /// Takes the I2C bus and the backpack's 7-bit address. Nothing is sent until
+    /// [`init`](Ht16k33::init).
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Ht16k33 {
+            i2c,
+            address,
+            frame: [Segments::NONE; 4],
+            indicators: 0,
+            blink_rate: BlinkRate::Off,
+            on: true,
+        }
+    }
+
+    /// Hands back the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Starts the oscillator, clears the display and turns it on at full brightness without
+    /// blinking.
+    pub fn init(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.set_oscillator(true)?;
+        self.indicators = 0;
+        self.blank()?;
+        self.set_dimming(15)?;
+        self.blink_rate = BlinkRate::Off;
+        self.set_display_on(true)
+    }
+
+    /// Starts or stops the internal oscillator. With it stopped the chip is in standby and the
+    /// display is dark.
+    pub fn set_oscillator(&mut self, on: bool) -> Result<(), Error<I2C::Error>> {
+        self.command(SYSTEM_SETUP | if on { OSCILLATOR_ON } else { 0 })
+    }
+
+    /// Sets the brightness from 0 (1/16 duty) to 15 (16/16 duty).
+    pub fn set_dimming(&mut self, level: u8) -> Result<(), Error<I2C::Error>> {
+        self.command(DIMMING | level.min(15))
+    }
+
+    pub fn set_blink_rate(&mut self, rate: BlinkRate) -> Result<(), Error<I2C::Error>> {
+        self.blink_rate = rate;
+        self.send_display_setup()
+    }
+
+    /// Turns the display on or off without losing its contents.
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), Error<I2C::Error>> {
+        self.on = on;
+        self.send_display_setup()
+    }
+
+    /// Lights or clears the colon between digits 1 and 2.
+    pub fn set_colon(&mut self, on: bool) -> Result<(), Error<I2C::Error>> {
+        self.set_indicator(Indicator::Colon, on)
+    }
+
+    /// Lights or clears one of the segments outside the digits.
+    pub fn set_indicator(
+        &mut self,
+        indicator: Indicator,
+        on: bool,
+    ) -> Result<(), Error<I2C::Error>> {
+        if on {
+            self.indicators |= indicator.bit();
+        } else {
+            self.indicators &= !indicator.bit();
+        }
+        let indicators = self.indicators;
+        self.write_ram(&[INDICATORS, indicators])
+    }
+
+    fn send_display_setup(&mut self) -> Result<(), Error<I2C::Error>> {
+        let on = if self.on { DISPLAY_ON } else { 0 };
+        self.command(DISPLAY_SETUP | (self.blink_rate as u8) << 1 | on)
+    }
+
+    fn command(&mut self, command: u8) -> Result<(), Error<I2C::Error>> {
+        self.write_ram(&[command])
+    }
+
+    fn write_ram(&mut self, bytes: &[u8]) -> Result<(), Error<I2C::Error>> {
+        self.i2c.write(self.address, bytes).map_err(Error::Bus)
+    }
+}
+
+impl<I2C> DigitDisplay for Ht16k33<I2C>
+where
+    I2C: I2c,
+{
+    type Error = I2C::Error;
+
+    fn digits(&self) -> usize {
+        4
+    }
+
+    fn write_digit(
+        &mut self,
+        position: usize,
+        segments: Segments,
+    ) -> Result<(), Error<Self::Error>> {
+        let address = *DIGITS
+            .get(position)
+            .ok_or(Error::InvalidPosition(position))?;
+        self.frame[position] = segments;
+        self.write_ram(&[address, segments.bits()])
+    }
+
+    /// Writes the digits and indicators in one transfer.
+    fn write_digits(&mut self, digits: &[Segments]) -> Result<(), Error<Self::Error>> {
+        if digits.len() > 4 {
+            return Err(Error::InvalidPosition(digits.len() - 1));
+        }
+        self.frame[..digits.len()].copy_from_slice(digits);
+
+        // Each RAM row is two bytes wide, and only the low byte of each is wired up.
+        let mut ram = [0; 10];
+        for (&address, segments) in DIGITS.iter().zip(self.frame.iter()) {
+            ram[1 + address as usize] = segments.bits();
+        }
+        ram[1 + INDICATORS as usize] = self.indicators;
+        self.write_ram(&ram)
+    }
+
+    /// Clears the digits, leaving the colon and indicators as they are.
+    fn blank(&mut self) -> Result<(), Error<Self::Error>> {
+        self.write_digits(&[Segments::NONE; 4])
+    }
+}
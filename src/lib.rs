@@ -1,8 +1,103 @@
 #![no_std]
 
 extern crate embedded_hal;
+#[cfg(feature = "eh02")]
+extern crate embedded_hal_02;
 
-use embedded_hal::digital::v2::OutputPin;
+use embedded_hal::digital::{OutputPin, PinState};
+
+#[cfg(feature = "eh02")]
+pub mod eh02;
+mod blink;
+mod buffer;
+mod clock;
+mod error;
+pub mod expander;
+pub mod font;
+pub mod ht16k33;
+mod marquee;
+pub mod max7219;
+mod multi_digit;
+mod port;
+mod pwm;
+mod segments;
+mod shift_register;
+pub mod tm1637;
+pub mod tm1638;
+
+pub use blink::{Blink, Heartbeat};
+pub use buffer::{DigitBuffer, Radix};
+pub use clock::{ClockDigits, ClockFields, ClockFormat, ColonStyle};
+pub use error::Error;
+pub use marquee::{Marquee, ScrollDirection};
+pub use multi_digit::MultiDigit;
+pub use port::{OutputPort, PortPins};
+pub use pwm::{CommonPwm, WithPwmError, MAX_PWM_LEVEL};
+pub use segments::{Iter, Segment, SegmentMap, Segments};
+pub use shift_register::{ShiftRegister, ShiftRegisterChain};
+
+/// The eight output pins driving segments a to g and the decimal point p.
+///
+/// Implemented for a tuple of eight pins `(a, b, c, d, e, f, g, p)`, which may each be a
+/// different type, and for an array of eight pins sharing one type. Either way the pins
+/// must share an error type, and are set one at a time, so for a moment a mix of old and new
+/// segments is lit. [`PortPins`] avoids that by updating the pins of a GPIO port in one write.
+/// [`ShiftRegister`] drives the same eight lines through a 74HC595, and the [`expander`] module
+/// through an I2C port expander.
+pub trait SegmentPins {
+    type Error;
+
+    /// Drives the pin wired to `segment` to `state`.
+    fn set_pin(&mut self, segment: Segment, state: PinState) -> Result<(), Self::Error>;
+
+    /// Drives the pins whose bit is set in `mask` to the matching bit of `levels`, bit 0 being
+    /// the pin for `a` and bit 7 the pin for `p`. The other pins keep their state.
+    ///
+    /// The default sets one pin at a time. Backends that can update every pin in one go
+    /// should override it.
+    fn set_pins(&mut self, levels: u8, mask: u8) -> Result<(), Self::Error> {
+        for segment in Segments::from(mask) {
+            let high = Segments::from(levels).contains(segment.into());
+            self.set_pin(segment, PinState::from(high))?;
+        }
+        Ok(())
+    }
+}
+
+impl<A, B, C, D, E, F, G, P> SegmentPins for (A, B, C, D, E, F, G, P)
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    P: OutputPin<Error = A::Error>,
+{
+    type Error = A::Error;
+
+    fn set_pin(&mut self, segment: Segment, state: PinState) -> Result<(), Self::Error> {
+        match segment {
+            Segment::A => self.0.set_state(state),
+            Segment::B => self.1.set_state(state),
+            Segment::C => self.2.set_state(state),
+            Segment::D => self.3.set_state(state),
+            Segment::E => self.4.set_state(state),
+            Segment::F => self.5.set_state(state),
+            Segment::G => self.6.set_state(state),
+            Segment::P => self.7.set_state(state),
+        }
+    }
+}
+
+impl<P: OutputPin> SegmentPins for [P; 8] {
+    type Error = P::Error;
+
+    fn set_pin(&mut self, segment: Segment, state: PinState) -> Result<(), Self::Error> {
+        self[segment as usize].set_state(state)
+    }
+}
 
 ///An eight segment display that can display a single digit from 0x0 to 0xF at a time.
 /// Intended for use with the HDSP-H101 and HDSP-H103.
@@ -22,33 +117,28 @@ use embedded_hal::digital::v2::OutputPin;
 ///Some like the H101 have the segment turned on being pin-low, and some like the H103 have on being pin-high.
 ///Set the `high_on` boolean as appropriate.
 ///
+/// Every method stops at the first pin that fails to switch and returns its error as [`Error::Pin`].
+///
 /// # Examples
 ///```rust,ignore
 ///    // in this case using the `atsamd21_hal` library to provide access to the pins
 ///    // but any embedded-hal implementation should work
 ///    let mut peripherals = Peripherals::take().unwrap();
 ///    let mut pins = peripherals.PORT.split();
-///    let mut seg_a = pins.pa21.into_open_drain_output(&mut pins.port);
-///    let mut seg_b = pins.pa20.into_open_drain_output(&mut pins.port);
-///    let mut seg_c = pins.pa11.into_open_drain_output(&mut pins.port);
-///    let mut seg_d = pins.pb10.into_open_drain_output(&mut pins.port);
-///    let mut seg_e = pins.pb11.into_open_drain_output(&mut pins.port);
-///    let mut seg_f = pins.pa16.into_open_drain_output(&mut pins.port);
-///    let mut seg_g = pins.pa17.into_open_drain_output(&mut pins.port);
-///    let mut seg_p = pins.pa10.into_open_drain_output(&mut pins.port);
-///    let mut eight_segment = EightSegment {
-///        high_on: false,
-///        seg_a: &mut seg_a,
-///        seg_b: &mut seg_b,
-///        seg_c: &mut seg_c,
-///        seg_d: &mut seg_d,
-///        seg_e: &mut seg_e,
-///        seg_f: &mut seg_f,
-///        seg_g: &mut seg_g,
-///        seg_p: &mut seg_p,
-///    };
-///    eight_segment.blank(); // All segments off
-///    eight_segment.display(0xb, false); // Display 'b' with decimal point off
+///    let seg_a = pins.pa21.into_open_drain_output(&mut pins.port);
+///    let seg_b = pins.pa20.into_open_drain_output(&mut pins.port);
+///    let seg_c = pins.pa11.into_open_drain_output(&mut pins.port);
+///    let seg_d = pins.pb10.into_open_drain_output(&mut pins.port);
+///    let seg_e = pins.pb11.into_open_drain_output(&mut pins.port);
+///    let seg_f = pins.pa16.into_open_drain_output(&mut pins.port);
+///    let seg_g = pins.pa17.into_open_drain_output(&mut pins.port);
+///    let seg_p = pins.pa10.into_open_drain_output(&mut pins.port);
+///    let mut eight_segment = EightSegment::new(
+///        (seg_a, seg_b, seg_c, seg_d, seg_e, seg_f, seg_g, seg_p),
+///        false,
+///    );
+///    eight_segment.blank().unwrap(); // All segments off
+///    eight_segment.display(0xb, false).unwrap(); // Display 'b' with decimal point off
 ///```
 /// # RP PICO
 ///``` rust,ignore
@@ -69,56 +159,158 @@ use embedded_hal::digital::v2::OutputPin;
 ///     );
 ///     // Pins 6-9 are the the bottom four pins on the eight segment display
 ///     // Pins 10-13 are the top four pins on the eight segment display
-///     let mut seg_a = pins.gpio8.into_push_pull_output();
-///     let mut seg_b = pins.gpio9.into_push_pull_output();
-///     let mut seg_c = pins.gpio12.into_push_pull_output();
-///     let mut seg_d = pins.gpio11.into_push_pull_output();
-///     let mut seg_e = pins.gpio10.into_push_pull_output();
-///     let mut seg_f = pins.gpio7.into_push_pull_output();
-///     let mut seg_g = pins.gpio6.into_push_pull_output();
-///     let mut seg_p = pins.gpio13.into_push_pull_output();
+///     let seg_a = pins.gpio8.into_push_pull_output();
+///     let seg_b = pins.gpio9.into_push_pull_output();
+///     let seg_c = pins.gpio12.into_push_pull_output();
+///     let seg_d = pins.gpio11.into_push_pull_output();
+///     let seg_e = pins.gpio10.into_push_pull_output();
+///     let seg_f = pins.gpio7.into_push_pull_output();
+///     let seg_g = pins.gpio6.into_push_pull_output();
+///     let seg_p = pins.gpio13.into_push_pull_output();
 ///
-///     let mut eight_segment = EightSegment {
-///         high_on: true, 
-///         seg_a: &mut seg_a,
-///         seg_b: &mut seg_b,
-///         seg_c: &mut seg_c,
-///         seg_d: &mut seg_d,
-///         seg_e: &mut seg_e,
-///         seg_f: &mut seg_f,
-///         seg_g: &mut seg_g,
-///         seg_p: &mut seg_p,
-///         };
+///     let mut eight_segment = EightSegment::new(
+///         (seg_a, seg_b, seg_c, seg_d, seg_e, seg_f, seg_g, seg_p),
+///         true,
+///     );
 /// 
-///    eight_segment.blank(); // All segments off
-///    eight_segment.display(0xb, false); // Display 'b' with decimal point off
+///    eight_segment.blank().unwrap(); // All segments off
+///    eight_segment.display(0xb, false).unwrap(); // Display 'b' with decimal point off
 ///```
-/// 
-use embedded_hal::digital::v2::PinState;
-pub struct EightSegment<'a> {
+pub struct EightSegment<PINS, PWM = ()> {
     pub high_on: bool,
-    pub seg_a: &'a mut dyn OutputPin<Error = core::convert::Infallible>,
-    pub seg_b: &'a mut dyn OutputPin<Error = core::convert::Infallible>,
-    pub seg_c: &'a mut dyn OutputPin<Error = core::convert::Infallible>,
-    pub seg_d: &'a mut dyn OutputPin<Error = core::convert::Infallible>,
-    pub seg_e: &'a mut dyn OutputPin<Error = core::convert::Infallible>,
-    pub seg_f: &'a mut dyn OutputPin<Error = core::convert::Infallible>,
-    pub seg_g: &'a mut dyn OutputPin<Error = core::convert::Infallible>,
-    pub seg_p: &'a mut dyn OutputPin<Error = core::convert::Infallible>,
+    /// What [`display`](EightSegment::display) shows for a count above 0xF.
+    pub out_of_range: OutOfRange,
+    pins: PINS,
+    current: Segments,
+    /// Segments kept dark whatever `current` says, while a [`Blink`] is in its off phase.
+    hidden: Segments,
+    /// The pin levels last written, or `None` if they aren't known.
+    levels: Option<u8>,
+    brightness: u8,
+    /// How far through the software PWM period [`tick`](EightSegment::tick) has got.
+    phase: u8,
+    /// The PWM output on the common line, if there is one.
+    pwm: PWM,
 }
 
-impl<'a> EightSegment<'a> {
-    pub fn blank(&mut self) {
-        let _ = self.seg_a.set_state(PinState::from(self.high_on));
-        let _ = self.seg_b.set_state(PinState::from(self.high_on));
-        let _ = self.seg_c.set_state(PinState::from(self.high_on));
-        let _ = self.seg_d.set_state(PinState::from(self.high_on));
-        let _ = self.seg_e.set_state(PinState::from(self.high_on));
-        let _ = self.seg_f.set_state(PinState::from(self.high_on));
-        let _ = self.seg_g.set_state(PinState::from(self.high_on));
-        let _ = self.seg_p.set_state(PinState::from(self.high_on));
+/// The software PWM brightness of an [`EightSegment`] at full duty, which is where it starts.
+///
+/// With a [`CommonPwm`], brightness is instead set in hardware on the [`MAX_PWM_LEVEL`] scale.
+pub const MAX_BRIGHTNESS: u8 = 15;
+
+/// What [`EightSegment::display`] shows when asked for a count above 0xF.
+///
+/// Use [`EightSegment::try_display`] to get an [`Error::InvalidDigit`] instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutOfRange {
+    /// Light only the middle `g` segment, a dash that can't be mistaken for a digit. The default.
+    #[default]
+    ErrorGlyph,
+    /// Turn every segment off, including the decimal point.
+    Blank,
+    /// Light segments a to g, which reads as an 8.
+    AllOn,
+}
+
+impl<PINS: SegmentPins> EightSegment<PINS> {
+    /// Takes ownership of the segment pins. See [`SegmentPins`] for the accepted layouts.
+    pub fn new(pins: PINS, high_on: bool) -> Self {
+        EightSegment {
+            high_on,
+            out_of_range: OutOfRange::default(),
+            pins,
+            current: Segments::NONE,
+            hidden: Segments::NONE,
+            levels: None,
+            brightness: MAX_BRIGHTNESS,
+            phase: 0,
+            pwm: (),
+        }
+    }
+
+    /// Hands back the segment pins.
+    pub fn release(self) -> PINS {
+        self.pins
+    }
+
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Dims the display by software PWM, from 0 (off) to [`MAX_BRIGHTNESS`] (always on).
+    ///
+    /// Anything in between needs [`tick`](EightSegment::tick) to be called at a steady rate. The
+    /// PWM period is 15 ticks, so tick at 1.5kHz or faster to avoid visible flicker.
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), Error<PINS::Error>> {
+        self.brightness = level.min(MAX_BRIGHTNESS);
+        self.drive()
+    }
+
+    /// Advances the software PWM by one step, turning the segments on or off as the
+    /// brightness calls for. Call it from a timer interrupt.
+    pub fn tick(&mut self) -> Result<(), Error<PINS::Error>> {
+        self.phase = (self.phase + 1) % MAX_BRIGHTNESS;
+        self.drive()
+    }
+}
+
+impl<PINS: SegmentPins, PWM> EightSegment<PINS, PWM> {
+    /// Turns every segment off, including the decimal point.
+    pub fn blank(&mut self) -> Result<(), Error<PINS::Error>> {
+        self.write(Segments::NONE)
+    }
+
+    /// Turns every segment on, including the decimal point, as a lamp test.
+    pub fn all_on(&mut self) -> Result<(), Error<PINS::Error>> {
+        self.write(Segments::ALL)
+    }
+
+    /// The segments last written. Until the first write this is [`Segments::NONE`], whatever
+    /// the pins are showing. While dimmed, this is the pattern being shown, not whether it
+    /// happens to be lit at this point in the PWM period.
+    pub fn current(&self) -> Segments {
+        self.current
     }
 
+    /// Lights exactly the segments in `segments` and turns the rest off.
+    ///
+    /// Only the pins that differ from the last write are driven. The first write, and the
+    /// first after an error, drives all eight.
+    pub fn write(&mut self, segments: Segments) -> Result<(), Error<PINS::Error>> {
+        self.current = segments;
+        self.drive()
+    }
+
+    /// Drives all eight pins to show [`current`](EightSegment::current) again, for when their
+    /// state may have been lost, such as after a brown-out.
+    pub fn force_refresh(&mut self) -> Result<(), Error<PINS::Error>> {
+        self.levels = None;
+        self.drive()
+    }
+
+    /// Drives the pins that differ from what's wanted at this point in the PWM period.
+    fn drive(&mut self) -> Result<(), Error<PINS::Error>> {
+        let segments = if self.phase < self.brightness {
+            self.current & !self.hidden
+        } else {
+            Segments::NONE
+        };
+        let levels = if self.high_on { segments } else { !segments }.bits();
+        let changed = match self.levels {
+            Some(previous) => previous ^ levels,
+            None => 0xFF,
+        };
+        if changed == 0 {
+            return Ok(());
+        }
+        // If this fails part way through, the pins are in an unknown state.
+        self.levels = None;
+        self.pins.set_pins(levels, changed)?;
+        self.levels = Some(levels);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn set_segments(
         &mut self,
         seg_a_on: bool,
@@ -129,55 +321,133 @@ impl<'a> EightSegment<'a> {
         seg_f_on: bool,
         seg_g_on: bool,
         seg_p_on: bool,
-    ) {
-        let _ = self.seg_a.set_state(PinState::from(seg_a_on ^ !self.high_on));
-        let _ = self.seg_b.set_state(PinState::from(seg_b_on ^ !self.high_on));
-        let _ = self.seg_c.set_state(PinState::from(seg_c_on ^ !self.high_on));
-        let _ = self.seg_d.set_state(PinState::from(seg_d_on ^ !self.high_on));
-        let _ = self.seg_e.set_state(PinState::from(seg_e_on ^ !self.high_on));
-        let _ = self.seg_f.set_state(PinState::from(seg_f_on ^ !self.high_on));
-        let _ = self.seg_g.set_state(PinState::from(seg_g_on ^ !self.high_on));
-        let _ = self.seg_p.set_state(PinState::from(seg_p_on ^ !self.high_on));
-    }
-
-    pub fn display(&mut self, count: u8, seg_p_on: bool) {
-        let (
-            seg_a_on,
-            seg_f_on,
-            seg_b_on,
-            seg_g_on,
-            seg_e_on,
-            seg_c_on,
-            seg_d_on,
-        ) = match count {
-            0x0 => (true, true, true, false, true, true, true),
-            0x1 => (false, false, true, false, false, true, false),
-            0x2 => (true, false, true, true, true, false, true),
-            0x3 => (true, false, true, true, false, true, true),
-            0x4 => (false, true, true, true, false, true, false),
-            0x5 => (true, true, false, true, false, true, true),
-            0x6 => (true, true, false, true, true, true, true),
-            0x7 => (true, false, true, false, false, true, false),
-            0x8 => (true, true, true, true, true, true, true),
-            0x9 => (true, true, true, true, false, true, false),
-            0xA => (true, true, true, false, true, true, false),
-            0xB => (false, true, false, true, true, true, true),
-            0xC => (false, false, false, true, true, false, true),
-            0xD => (false, false, true, true, true, true, true),
-            0xE => (true, true, false, true, true, false, true),
-            0xF => (true, true, false, true, true, false, false),
-            _ => (true, true, true, true, true, true, true),
+    ) -> Result<(), Error<PINS::Error>> {
+        let mut segments = Segments::NONE;
+        segments.set(Segments::A, seg_a_on);
+        segments.set(Segments::B, seg_b_on);
+        segments.set(Segments::C, seg_c_on);
+        segments.set(Segments::D, seg_d_on);
+        segments.set(Segments::E, seg_e_on);
+        segments.set(Segments::F, seg_f_on);
+        segments.set(Segments::G, seg_g_on);
+        segments.set(Segments::P, seg_p_on);
+        self.write(segments)
+    }
+
+    /// Shows `count` as a hex digit. Counts above 0xF are shown according to `out_of_range`.
+    pub fn display(&mut self, count: u8, seg_p_on: bool) -> Result<(), Error<PINS::Error>> {
+        let mut segments = match Segments::hex(count) {
+            Some(segments) => segments,
+            None => match self.out_of_range {
+                OutOfRange::ErrorGlyph => Segments::G,
+                OutOfRange::Blank => return self.write(Segments::NONE),
+                OutOfRange::AllOn => !Segments::P,
+            },
         };
+        segments.set(Segments::P, seg_p_on);
+        self.write(segments)
+    }
+
+    /// Shows `count` as a hex digit, or returns [`Error::InvalidDigit`] without touching the pins
+    /// if it's above 0xF.
+    pub fn try_display(&mut self, count: u8, seg_p_on: bool) -> Result<(), Error<PINS::Error>> {
+        let mut segments = Segments::hex(count).ok_or(Error::InvalidDigit(count))?;
+        segments.set(Segments::P, seg_p_on);
+        self.write(segments)
+    }
+
+    /// Shows `c` using the glyphs in [`font`], or returns [`Error::UnsupportedChar`] without
+    /// touching the pins if it has none.
+    ///
+    /// To show a fallback instead, look the glyph up yourself:
+    /// `display.write(font::glyph(c).unwrap_or(Segments::G))`.
+    pub fn display_char(&mut self, c: char, seg_p_on: bool) -> Result<(), Error<PINS::Error>> {
+        let mut segments = font::glyph(c).ok_or(Error::UnsupportedChar(c))?;
+        if seg_p_on {
+            segments |= Segments::P;
+        }
+        self.write(segments)
+    }
+
+    /// Turns the decimal point on or off, keeping the rest of the digit shown.
+    pub fn set_dp(&mut self, on: bool) -> Result<(), Error<PINS::Error>> {
+        let mut segments = self.current;
+        segments.set(Segments::P, on);
+        self.write(segments)
+    }
+
+    /// Flips the decimal point, keeping the rest of the digit shown.
+    pub fn toggle_dp(&mut self) -> Result<(), Error<PINS::Error>> {
+        self.write(self.current ^ Segments::P)
+    }
+}
+
+/// A display with several digits, numbered from 0 on the left.
+///
+/// Implementors only provide [`digits`](DigitDisplay::digits) and
+/// [`write_digit`](DigitDisplay::write_digit); the hex and character helpers mirror the ones on
+/// [`EightSegment`].
+///
+/// Digits are always [`Segments`]. Controllers that store them in the same `pgfedcba` bit order,
+/// like the TM1637, TM1638 and HT16K33, are sent the bits unchanged, and the others, like the
+/// MAX7219 in no-decode mode, have them rearranged by their driver.
+pub trait DigitDisplay {
+    type Error;
+
+    /// How many digits the display has.
+    fn digits(&self) -> usize;
 
-        self.set_segments(
-            seg_a_on,
-            seg_b_on,
-            seg_c_on,
-            seg_d_on,
-            seg_e_on,
-            seg_f_on,
-            seg_g_on,
-            seg_p_on,
-        );
-    }
-}
\ No newline at end of file
+    /// Lights exactly `segments` on the digit at `position`.
+    fn write_digit(
+        &mut self,
+        position: usize,
+        segments: Segments,
+    ) -> Result<(), Error<Self::Error>>;
+
+    /// Writes `digits` starting from the leftmost digit.
+    fn write_digits(&mut self, digits: &[Segments]) -> Result<(), Error<Self::Error>> {
+        if digits.len() > self.digits() {
+            return Err(Error::InvalidPosition(digits.len() - 1));
+        }
+        for (position, &segments) in digits.iter().enumerate() {
+            self.write_digit(position, segments)?;
+        }
+        Ok(())
+    }
+
+    /// Turns every segment of every digit off.
+    fn blank(&mut self) -> Result<(), Error<Self::Error>> {
+        for position in 0..self.digits() {
+            self.write_digit(position, Segments::NONE)?;
+        }
+        Ok(())
+    }
+
+    /// Shows `count` as a hex digit at `position`, or returns [`Error::InvalidDigit`] if it's
+    /// above 0xF.
+    fn display(
+        &mut self,
+        position: usize,
+        count: u8,
+        seg_p_on: bool,
+    ) -> Result<(), Error<Self::Error>> {
+        let mut segments = Segments::hex(count).ok_or(Error::InvalidDigit(count))?;
+        segments.set(Segments::P, seg_p_on);
+        self.write_digit(position, segments)
+    }
+
+    /// Shows `c` at `position` using the glyphs in [`font`], or returns
+    /// [`Error::UnsupportedChar`] if it has none.
+    fn display_char(
+        &mut self,
+        position: usize,
+        c: char,
+        seg_p_on: bool,
+    ) -> Result<(), Error<Self::Error>> {
+        let mut segments = font::glyph(c).ok_or(Error::UnsupportedChar(c))?;
+        if seg_p_on {
+            segments |= Segments::P;
+        }
+        self.write_digit(position, segments)
+    }
+}
@@ -0,0 +1,163 @@
+use core::iter;
+
+use {font, DigitDisplay, Error, Segments};
+
+/// Which way a [`Marquee`] moves its text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScrollDirection {
+    /// Start at the beginning of the text and move it to the left, the way it's read. The
+    /// default.
+    #[default]
+    Left,
+    /// Start at the end of the text and move it to the right.
+    Right,
+}
+
+/// Scrolls text too long for an `N` digit display across it without blocking.
+///
+/// Call [`tick`](Marquee::tick) regularly with a millisecond timestamp. The first `N` digits of
+/// the text are shown for [`pause_ms`](Marquee::pause_ms), then the text moves one digit every
+/// [`step_ms`](Marquee::step_ms) until its end is showing, which also stays for `pause_ms`.
+/// After that it starts over if [`looping`](Marquee::looping), or stops with
+/// [`finished`](Marquee::finished) set.
+///
+/// Characters are looked up in [`font`], with a `'.'` lighting the point of the character before
+/// it like [`DigitBuffer`](::DigitBuffer) does. Characters with no glyph are left blank. Text
+/// that already fits isn't scrolled, but without looping it's still held for `pause_ms` before
+/// the marquee finishes.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::Marquee;
+///
+///    let mut marquee = Marquee::<4>::new("SErvicE duE");
+///    marquee.step_ms = 300;
+///
+///    // then in the main loop
+///    marquee.tick(&mut display, millis()).unwrap();
+///    if marquee.finished() {
+///        marquee.set_text("CALL");
+///    }
+///```
+pub struct Marquee<'a, const N: usize> {
+    pub direction: ScrollDirection,
+    /// How long each digit of movement takes, in milliseconds.
+    pub step_ms: u32,
+    /// How long the start and the end of the text are held, in milliseconds.
+    pub pause_ms: u32,
+    /// Whether to start over after the end of the text, rather than stopping there.
+    pub looping: bool,
+    text: &'a str,
+    /// How many digits the text has moved from where it started.
+    offset: usize,
+    /// When the text last moved, or `None` if it hasn't been drawn yet.
+    moved_ms: Option<u32>,
+    finished: bool,
+}
+
+impl<'a, const N: usize> Marquee<'a, N> {
+    /// Scrolls `text` left, moving every 250ms with a 1s pause at each end, and loops.
+    pub fn new(text: &'a str) -> Self {
+        Marquee {
+            direction: ScrollDirection::default(),
+            step_ms: 250,
+            pause_ms: 1000,
+            looping: true,
+            text,
+            offset: 0,
+            moved_ms: None,
+            finished: false,
+        }
+    }
+
+    /// Replaces the text and starts it from the beginning.
+    pub fn set_text(&mut self, text: &'a str) {
+        self.text = text;
+        self.restart();
+    }
+
+    /// Moves the text back to where it starts, to be drawn on the next tick.
+    pub fn restart(&mut self) {
+        self.offset = 0;
+        self.moved_ms = None;
+        self.finished = false;
+    }
+
+    /// Whether a marquee that isn't looping has reached the end of its text and held it there
+    /// for the pause.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The digits showing now, leftmost first.
+    pub fn frame(&self) -> [Segments; N] {
+        let steps = self.steps();
+        let start = match self.direction {
+            ScrollDirection::Left => self.offset,
+            ScrollDirection::Right => steps - self.offset,
+        };
+        let mut frame = [Segments::NONE; N];
+        for (digit, segments) in frame.iter_mut().zip(glyphs(self.text).skip(start)) {
+            *digit = segments;
+        }
+        frame
+    }
+
+    /// Moves the text on if it's due at the time `now_ms`, and writes the first `N` digits of
+    /// `display` whenever they change. The timestamp may wrap around.
+    pub fn tick<D: DigitDisplay>(
+        &mut self,
+        display: &mut D,
+        now_ms: u32,
+    ) -> Result<(), Error<D::Error>> {
+        let moved_ms = match self.moved_ms {
+            Some(moved_ms) => moved_ms,
+            None => {
+                self.moved_ms = Some(now_ms);
+                return display.write_digits(&self.frame());
+            }
+        };
+        let steps = self.steps();
+        if self.finished || (steps == 0 && self.looping) {
+            return Ok(());
+        }
+        let wait_ms = if self.offset == 0 || self.offset == steps {
+            self.pause_ms
+        } else {
+            self.step_ms
+        };
+        if now_ms.wrapping_sub(moved_ms) < wait_ms {
+            return Ok(());
+        }
+
+        if self.offset < steps {
+            self.offset += 1;
+        } else if self.looping {
+            self.offset = 0;
+        } else {
+            self.finished = true;
+            return Ok(());
+        }
+        self.moved_ms = Some(now_ms);
+        display.write_digits(&self.frame())
+    }
+
+    /// How many digits the text moves from start to end.
+    fn steps(&self) -> usize {
+        glyphs(self.text).count().saturating_sub(N)
+    }
+}
+
+/// The digits `text` takes up, with each `'.'` merged into the character before it.
+fn glyphs<'a>(text: &'a str) -> impl Iterator<Item = Segments> + 'a {
+    let mut chars = text.chars().peekable();
+    iter::from_fn(move || {
+        let c = chars.next()?;
+        let mut segments = font::glyph(c).unwrap_or(Segments::NONE);
+        if c != '.' && chars.peek() == Some(&'.') {
+            chars.next();
+            segments |= Segments::P;
+        }
+        Some(segments)
+    })
+}
@@ -0,0 +1,191 @@
+//! Driver for the MAX7219 and MAX7221 eight-digit LED display controllers.
+
+use core::array;
+
+use embedded_hal::spi::{Operation, SpiDevice};
+
+use {DigitDisplay, Error, SegmentMap, Segments};
+
+const NO_OP: u8 = 0x00;
+const DIGIT_0: u8 = 0x01;
+const DECODE_MODE: u8 = 0x09;
+const INTENSITY: u8 = 0x0A;
+const SCAN_LIMIT: u8 = 0x0B;
+const SHUTDOWN: u8 = 0x0C;
+const DISPLAY_TEST: u8 = 0x0F;
+
+/// In no-decode mode the chip wants `p` in D7, then `a` in D6 down to `g` in D0.
+const NO_DECODE: SegmentMap = SegmentMap::new([6, 5, 4, 3, 2, 1, 0, 7]);
+
+/// `CHIPS` daisy-chained MAX7219 or MAX7221 controllers, each driving eight digits, behind one
+/// SPI chip select.
+///
+/// Positions run left to right across the whole chain, eight per chip. Chip 0 is the one whose
+/// DIN is wired to the MCU, and within a chip position 0 is DIG7, as on the common eight-digit
+/// modules where DIG0 is the rightmost digit.
+///
+/// Digits written through [`DigitDisplay`] are sent as raw segments, so those digits must have
+/// decoding turned off, which is how [`init`](Max7219::init) leaves them.
+///
+/// The SPI device should use mode 0 at up to 10MHz.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::max7219::Max7219;
+///    use eight_segment::DigitDisplay;
+///
+///    let mut display: Max7219<_, 2> = Max7219::new(spi_device);
+///    display.init().unwrap();
+///    display.set_intensity(4).unwrap();
+///    display.display_char(0, 'H', false).unwrap();
+///    display.display(15, 0x7, true).unwrap();
+///```
+pub struct Max7219<SPI, const CHIPS: usize = 1> {
+    spi: SPI,
+}
+
+impl<SPI, const CHIPS: usize> Max7219<SPI, CHIPS>
+where
+    SPI: SpiDevice,
+{
+    /// Takes the SPI device. Nothing is sent until [`init`](Max7219::init).
+    pub fn new(spi: SPI) -> Self {
+        Max7219 { spi }
+    }
+
+    /// Hands back the SPI device.
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Wakes every chip with display test off, all eight digits scanned, decoding off,
+    /// intensity at its lowest and every digit blank.
+    pub fn init(&mut self) -> Result<(), Error<SPI::Error>> {
+        self.set_display_test(false)?;
+        self.set_scan_limit(8)?;
+        self.set_decode_mode(0)?;
+        self.set_intensity(0)?;
+        self.blank()?;
+        self.set_shutdown(false)
+    }
+
+    /// Sets the brightness of every chip, from 0 (1/32 duty) to 15 (31/32 duty).
+    pub fn set_intensity(&mut self, level: u8) -> Result<(), Error<SPI::Error>> {
+        self.write_all(INTENSITY, level.min(15))
+    }
+
+    /// Scans only the first `digits` digit registers of each chip, from 1 to 8. Fewer digits
+    /// means each is lit for longer, so limit this to the digits that are wired up.
+    pub fn set_scan_limit(&mut self, digits: u8) -> Result<(), Error<SPI::Error>> {
+        self.write_all(SCAN_LIMIT, digits.clamp(1, 8) - 1)
+    }
+
+    /// Turns on Code B decoding for the digit registers set in `mask`, bit 0 being DIG0.
+    pub fn set_decode_mode(&mut self, mask: u8) -> Result<(), Error<SPI::Error>> {
+        self.write_all(DECODE_MODE, mask)
+    }
+
+    /// Puts every chip into or out of its low-power shutdown mode, which turns the display off
+    /// but keeps its contents.
+    pub fn set_shutdown(&mut self, shutdown: bool) -> Result<(), Error<SPI::Error>> {
+        self.write_all(SHUTDOWN, !shutdown as u8)
+    }
+
+    /// Lights every segment at full brightness while `on`, whatever the other registers say.
+    pub fn set_display_test(&mut self, on: bool) -> Result<(), Error<SPI::Error>> {
+        self.write_all(DISPLAY_TEST, on as u8)
+    }
+
+    /// Sends a Code B character to a digit that has decoding turned on.
+    ///
+    /// `code` is 0 to 9 for the digits, then 0xA for `-`, 0xB for `E`, 0xC for `H`, 0xD for `L`,
+    /// 0xE for `P` and 0xF for blank. Anything larger is an [`Error::InvalidDigit`].
+    pub fn write_code_b(
+        &mut self,
+        position: usize,
+        code: u8,
+        seg_p_on: bool,
+    ) -> Result<(), Error<SPI::Error>> {
+        if code > 0xF {
+            return Err(Error::InvalidDigit(code));
+        }
+        let (chip, register) = self.locate(position)?;
+        self.write_one(chip, register, code | (seg_p_on as u8) << 7)
+    }
+
+    fn locate(&self, position: usize) -> Result<(usize, u8), Error<SPI::Error>> {
+        if position >= CHIPS * 8 {
+            return Err(Error::InvalidPosition(position));
+        }
+        Ok((position / 8, DIGIT_0 + 7 - (position % 8) as u8))
+    }
+
+    /// Writes `data` to `register` on every chip.
+    fn write_all(&mut self, register: u8, data: u8) -> Result<(), Error<SPI::Error>> {
+        self.send([[register, data]; CHIPS])
+    }
+
+    /// Writes `data` to `register` on one chip, and a no-op to the rest.
+    fn write_one(&mut self, chip: usize, register: u8, data: u8) -> Result<(), Error<SPI::Error>> {
+        let mut words = [[NO_OP, 0]; CHIPS];
+        words[chip] = [register, data];
+        self.send(words)
+    }
+
+    /// Shifts one word per chip through the chain and latches them together.
+    fn send(&mut self, words: [[u8; 2]; CHIPS]) -> Result<(), Error<SPI::Error>> {
+        // The first word out ends up in the chip furthest from the MCU.
+        let mut operations: [Operation<u8>; CHIPS] =
+            array::from_fn(|i| Operation::Write(&words[CHIPS - 1 - i]));
+        self.spi.transaction(&mut operations).map_err(Error::Bus)
+    }
+}
+
+impl<SPI, const CHIPS: usize> DigitDisplay for Max7219<SPI, CHIPS>
+where
+    SPI: SpiDevice,
+{
+    type Error = SPI::Error;
+
+    fn digits(&self) -> usize {
+        CHIPS * 8
+    }
+
+    fn write_digit(
+        &mut self,
+        position: usize,
+        segments: Segments,
+    ) -> Result<(), Error<Self::Error>> {
+        let (chip, register) = self.locate(position)?;
+        self.write_one(chip, register, NO_DECODE.to_bits(segments))
+    }
+
+    /// Updates each digit register on every chip in one transfer, eight transfers in all.
+    fn write_digits(&mut self, digits: &[Segments]) -> Result<(), Error<Self::Error>> {
+        if digits.len() > CHIPS * 8 {
+            return Err(Error::InvalidPosition(digits.len() - 1));
+        }
+        for offset in 0..8 {
+            let mut words = [[NO_OP, 0]; CHIPS];
+            let mut any = false;
+            for (chip, word) in words.iter_mut().enumerate() {
+                if let Some(&segments) = digits.get(chip * 8 + offset) {
+                    *word = [DIGIT_0 + 7 - offset as u8, NO_DECODE.to_bits(segments)];
+                    any = true;
+                }
+            }
+            if any {
+                self.send(words)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Clears every digit register. Digits with decoding on will show 0 rather than blank.
+    fn blank(&mut self) -> Result<(), Error<Self::Error>> {
+        for register in DIGIT_0..DIGIT_0 + 8 {
+            self.write_all(register, 0)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use embedded_hal::digital::{OutputPin, PinState};
+
+use {DigitDisplay, EightSegment, Error, SegmentPins, Segments};
+
+/// A multiplexed display of `N` digits that share one set of segment lines, with a select pin
+/// per digit, like the common 4- and 8-digit modules.
+///
+/// Only one digit is lit at a time. Digits are drawn into a frame buffer with the
+/// [`DigitDisplay`] methods, and [`refresh`](MultiDigit::refresh) moves on to the next digit, so
+/// it should be called from a timer interrupt often enough that the display doesn't flicker,
+/// around 60 times a second per digit or faster.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::{DigitDisplay, EightSegment, MultiDigit};
+///
+///    let segments = EightSegment::new((seg_a, seg_b, seg_c, seg_d, seg_e, seg_f, seg_g, seg_p), true);
+///    // Common-cathode digits switched through NPN transistors are selected by driving high.
+///    let mut display = MultiDigit::new(segments, [dig_1, dig_2, dig_3, dig_4], true);
+///    display.blank().unwrap();
+///    display.display(0, 0x1, false).unwrap();
+///    display.display_char(3, 'H', false).unwrap();
+///
+///    // then in the timer interrupt
+///    display.refresh().unwrap();
+///```
+pub struct MultiDigit<PINS, SEL, const N: usize> {
+    /// Whether a digit is selected by driving its select pin high.
+    pub select_high: bool,
+    /// Whether to turn the segments off before switching digits. Slow digit drivers can
+    /// otherwise show a faint copy of one digit on its neighbour.
+    pub blank_between: bool,
+    segments: EightSegment<PINS>,
+    selects: [SEL; N],
+    frame: [Segments; N],
+    current: usize,
+}
+
+impl<PINS, SEL, const N: usize> MultiDigit<PINS, SEL, N>
+where
+    PINS: SegmentPins,
+    SEL: OutputPin<Error = PINS::Error>,
+{
+    /// Takes ownership of the shared segment lines and the digit select pins, leftmost digit
+    /// first. No pins are touched until the first [`refresh`](MultiDigit::refresh) or
+    /// [`blank`](DigitDisplay::blank). `N` must be at least 1, which is checked at compile
+    /// time.
+    pub fn new(segments: EightSegment<PINS>, selects: [SEL; N], select_high: bool) -> Self {
+        const { assert!(N > 0, "a MultiDigit needs at least one digit") };
+        MultiDigit {
+            select_high,
+            blank_between: true,
+            segments,
+            selects,
+            frame: [Segments::NONE; N],
+            current: N - 1,
+        }
+    }
+
+    /// Hands back the segment lines and the select pins.
+    pub fn release(self) -> (EightSegment<PINS>, [SEL; N]) {
+        (self.segments, self.selects)
+    }
+
+    /// The segments waiting to be shown on each digit.
+    pub fn frame(&self) -> &[Segments; N] {
+        &self.frame
+    }
+
+    /// Shows the next digit of the frame, turning the previous one off.
+    pub fn refresh(&mut self) -> Result<(), Error<PINS::Error>> {
+        let previous = self.current;
+        self.current = (self.current + 1) % N;
+
+        if self.blank_between {
+            self.segments.blank()?;
+        }
+        self.selects[previous].set_state(PinState::from(!self.select_high))?;
+        self.segments.write(self.frame[self.current])?;
+        self.selects[self.current].set_state(PinState::from(self.select_high))?;
+        Ok(())
+    }
+}
+
+impl<PINS, SEL, const N: usize> DigitDisplay for MultiDigit<PINS, SEL, N>
+where
+    PINS: SegmentPins,
+    SEL: OutputPin<Error = PINS::Error>,
+{
+    type Error = PINS::Error;
+
+    fn digits(&self) -> usize {
+        N
+    }
+
+    /// Updates the frame buffer. The digit changes on its next [`refresh`](MultiDigit::refresh).
+    fn write_digit(
+        &mut self,
+        position: usize,
+        segments: Segments,
+    ) -> Result<(), Error<Self::Error>> {
+        let digit = self
+            .frame
+            .get_mut(position)
+            .ok_or(Error::InvalidPosition(position))?;
+        *digit = segments;
+        Ok(())
+    }
+
+    /// Clears the frame buffer and deselects every digit.
+    fn blank(&mut self) -> Result<(), Error<Self::Error>> {
+        self.frame = [Segments::NONE; N];
+        for select in self.selects.iter_mut() {
+            select.set_state(PinState::from(!self.select_high))?;
+        }
+        self.segments.blank()
+    }
+}
@@ -0,0 +1,90 @@
+use embedded_hal::digital::PinState;
+
+use {Segment, SegmentPins, Segments};
+
+/// A GPIO port that can change several of its pins in a single write, for example through a
+/// bit set/reset register, so no intermediate state is ever visible.
+///
+/// Implement it for your HAL's port to drive the segments through [`PortPins`].
+///
+/// # Examples
+///```rust,ignore
+///    use core::convert::Infallible;
+///    use eight_segment::OutputPort;
+///
+///    struct GpioB(stm32f1::stm32f103::GPIOB);
+///
+///    impl OutputPort for GpioB {
+///        type Error = Infallible;
+///
+///        fn write_masked(&mut self, mask: u32, levels: u32) -> Result<(), Infallible> {
+///            let set = mask & levels;
+///            let reset = mask & !levels;
+///            self.0.bsrr.write(|w| unsafe { w.bits(reset << 16 | set) });
+///            Ok(())
+///        }
+///    }
+///```
+pub trait OutputPort {
+    type Error;
+
+    /// Drives the pins set in `mask` to the matching bits of `levels`, bit 0 being pin 0 of the
+    /// port, and leaves every other pin alone.
+    fn write_masked(&mut self, mask: u32, levels: u32) -> Result<(), Self::Error>;
+}
+
+/// Segment pins that all sit on one [`OutputPort`], so every segment changes at once.
+///
+///```rust,ignore
+///    use eight_segment::{EightSegment, PortPins};
+///
+///    // a to g on PB0 to PB6, p on PB8
+///    let pins = PortPins::new(GpioB(dp.GPIOB), [0, 1, 2, 3, 4, 5, 6, 8]);
+///    let mut eight_segment = EightSegment::new(pins, true);
+///```
+pub struct PortPins<PORT> {
+    port: PORT,
+    pins: [u8; 8],
+}
+
+impl<PORT: OutputPort> PortPins<PORT> {
+    /// Takes the port and the pin number, 0 to 31, of each segment in the order `a` to `g`
+    /// then `p`.
+    ///
+    /// # Panics
+    /// If any pin number is above 31.
+    pub fn new(port: PORT, pins: [u8; 8]) -> Self {
+        assert!(
+            pins.iter().all(|&pin| pin < 32),
+            "port pins run from 0 to 31"
+        );
+        PortPins { port, pins }
+    }
+
+    /// Hands back the port.
+    pub fn release(self) -> PORT {
+        self.port
+    }
+}
+
+impl<PORT: OutputPort> SegmentPins for PortPins<PORT> {
+    type Error = PORT::Error;
+
+    fn set_pin(&mut self, segment: Segment, state: PinState) -> Result<(), Self::Error> {
+        let mask = 1 << self.pins[segment as usize];
+        let levels = if state == PinState::High { mask } else { 0 };
+        self.port.write_masked(mask, levels)
+    }
+
+    fn set_pins(&mut self, levels: u8, mask: u8) -> Result<(), Self::Error> {
+        let (mut port_mask, mut port_levels) = (0, 0);
+        for segment in Segments::from(mask) {
+            let bit = 1 << self.pins[segment as usize];
+            port_mask |= bit;
+            if Segments::from(levels).contains(segment.into()) {
+                port_levels |= bit;
+            }
+        }
+        self.port.write_masked(port_mask, port_levels)
+    }
+}
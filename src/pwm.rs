@@ -0,0 +1,179 @@
+use core::fmt;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::pwm::SetDutyCycle;
+
+use {EightSegment, Error, SegmentPins};
+
+/// The PWM level of an [`EightSegment`] with a [`CommonPwm`] at full duty.
+///
+/// Hardware PWM has far finer steps than the software PWM's [`MAX_BRIGHTNESS`](::MAX_BRIGHTNESS),
+/// so it has its own scale, set with [`set_pwm_level`](EightSegment::set_pwm_level).
+pub const MAX_PWM_LEVEL: u8 = u8::MAX;
+
+/// A PWM output switching the display's common anode or cathode, which dims every segment in
+/// hardware. Added with [`EightSegment::with_pwm`].
+///
+/// Its brightness is set with [`set_pwm_level`](EightSegment::set_pwm_level) rather than
+/// `set_brightness`, because it runs from 0 to [`MAX_PWM_LEVEL`] instead of the software PWM's 0
+/// to [`MAX_BRIGHTNESS`](::MAX_BRIGHTNESS). Sharing the name would let code written for one scale
+/// compile unchanged, and quietly misbehave, against the other.
+pub struct CommonPwm<P> {
+    pwm: P,
+    inverted: bool,
+    /// The level last set, or `None` until [`set_pwm_level`](EightSegment::set_pwm_level) is
+    /// first called.
+    level: Option<u8>,
+}
+
+/// Returned by [`EightSegment::with_pwm`] when the segment pins couldn't be driven, along with
+/// the display and PWM output it was given, so that neither is lost.
+pub struct WithPwmError<PINS: SegmentPins, P> {
+    pub error: Error<PINS::Error>,
+    pub display: EightSegment<PINS>,
+    pub pwm: P,
+}
+
+impl<PINS: SegmentPins, P> fmt::Debug for WithPwmError<PINS, P>
+where
+    PINS::Error: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WithPwmError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<PINS: SegmentPins> EightSegment<PINS> {
+    /// Adds a PWM output on the display's common line, so that
+    /// [`set_pwm_level`](EightSegment::set_pwm_level) dims it in hardware instead of by software
+    /// PWM. The duty cycle is left alone until `set_pwm_level` is first called.
+    ///
+    /// Software PWM is turned off, so the segments are driven again in case it had left them
+    /// dark. If that fails, the display and the PWM output come back in the error.
+    ///
+    /// Set `inverted` if the display is lit while the PWM output is low, for example when it
+    /// drives a PNP transistor on a common anode.
+    ///
+    /// # Examples
+    ///```rust,ignore
+    ///    let mut eight_segment = EightSegment::new(pins, false)
+    ///        .with_pwm(pwm_channel, false)
+    ///        .unwrap();
+    ///    eight_segment.set_pwm_level(0).unwrap();
+    ///    eight_segment.display(0x4, false).unwrap();
+    ///    eight_segment.fade_in(500, &mut delay).unwrap();
+    ///```
+    pub fn with_pwm<P: SetDutyCycle>(
+        mut self,
+        pwm: P,
+        inverted: bool,
+    ) -> Result<EightSegment<PINS, CommonPwm<P>>, WithPwmError<PINS, P>> {
+        let (brightness, phase) = (self.brightness, self.phase);
+        self.brightness = ::MAX_BRIGHTNESS;
+        self.phase = 0;
+        if let Err(error) = self.drive() {
+            self.brightness = brightness;
+            self.phase = phase;
+            return Err(WithPwmError {
+                error,
+                display: self,
+                pwm,
+            });
+        }
+        Ok(EightSegment {
+            high_on: self.high_on,
+            out_of_range: self.out_of_range,
+            pins: self.pins,
+            current: self.current,
+            hidden: self.hidden,
+            levels: self.levels,
+            brightness: self.brightness,
+            phase: self.phase,
+            pwm: CommonPwm {
+                pwm,
+                inverted,
+                level: None,
+            },
+        })
+    }
+}
+
+impl<PINS: SegmentPins, P: SetDutyCycle> EightSegment<PINS, CommonPwm<P>> {
+    /// Hands back the segment pins and the PWM output.
+    pub fn release(self) -> (PINS, P) {
+        (self.pins, self.pwm.pwm)
+    }
+
+    /// The level last set, or `None` if [`set_pwm_level`](EightSegment::set_pwm_level) hasn't
+    /// been called yet.
+    pub fn pwm_level(&self) -> Option<u8> {
+        self.pwm.level
+    }
+
+    /// Sets the hardware PWM level from 0 (off) to [`MAX_PWM_LEVEL`] (full). The duty cycle
+    /// follows a gamma curve, so equal steps in `level` look like equal steps in brightness.
+    pub fn set_pwm_level(&mut self, level: u8) -> Result<(), Error<P::Error>> {
+        let max = u32::from(self.pwm.pwm.max_duty_cycle());
+        let (level32, full) = (u32::from(level), u32::from(MAX_PWM_LEVEL));
+        let duty = (max * level32 * level32 / (full * full)) as u16;
+        let duty = if self.pwm.inverted {
+            max as u16 - duty
+        } else {
+            duty
+        };
+        self.pwm.pwm.set_duty_cycle(duty).map_err(Error::Pwm)?;
+        self.pwm.level = Some(level);
+        Ok(())
+    }
+
+    /// Steps the PWM level to `level` over `duration_ms` milliseconds, blocking until it gets
+    /// there. If the level hasn't been set yet, there is nothing to fade from, so it's set to
+    /// `level` straight away.
+    pub fn fade_to(
+        &mut self,
+        level: u8,
+        duration_ms: u32,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<P::Error>> {
+        let mut current = match self.pwm.level {
+            Some(current) => current,
+            None => return self.set_pwm_level(level),
+        };
+        let steps = u32::from(current.max(level) - current.min(level));
+        if steps == 0 {
+            return Ok(());
+        }
+        let step_us = duration_ms.saturating_mul(1000) / steps;
+        while current != level {
+            current = if current < level {
+                current + 1
+            } else {
+                current - 1
+            };
+            self.set_pwm_level(current)?;
+            delay.delay_us(step_us);
+        }
+        Ok(())
+    }
+
+    /// Fades from off up to full brightness over `duration_ms` milliseconds.
+    pub fn fade_in(
+        &mut self,
+        duration_ms: u32,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<P::Error>> {
+        self.set_pwm_level(0)?;
+        self.fade_to(MAX_PWM_LEVEL, duration_ms, delay)
+    }
+
+    /// Fades from the current PWM level down to off over `duration_ms` milliseconds.
+    pub fn fade_out(
+        &mut self,
+        duration_ms: u32,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<P::Error>> {
+        self.fade_to(0, duration_ms, delay)
+    }
+}
@@ -0,0 +1,254 @@
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+/// One of the eight segments of the display, labelled as in the HDSP-H101 and H103 datasheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Segment {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    /// The decimal point.
+    P,
+}
+
+impl Segment {
+    /// Every segment, in the order `a` to `g` then `p`.
+    pub const ALL: [Segment; 8] = [
+        Segment::A,
+        Segment::B,
+        Segment::C,
+        Segment::D,
+        Segment::E,
+        Segment::F,
+        Segment::G,
+        Segment::P,
+    ];
+}
+
+/// A set of lit segments, stored as one bit per segment.
+///
+/// The bits follow the usual `pgfedcba` order: bit 0 is segment `a`, bit 6 is `g` and bit 7 is
+/// the decimal point `p`, so `Segments::from(0x3F)` is the digit 0.
+///```rust
+///    use eight_segment::Segments;
+///
+///    let one = Segments::B | Segments::C;
+///    assert_eq!(u8::from(one | Segments::P), 0x86);
+///    assert!((one & !Segments::B).contains(Segments::C));
+///```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Segments(u8);
+
+impl Segments {
+    pub const NONE: Segments = Segments(0);
+    pub const A: Segments = Segments(1 << 0);
+    pub const B: Segments = Segments(1 << 1);
+    pub const C: Segments = Segments(1 << 2);
+    pub const D: Segments = Segments(1 << 3);
+    pub const E: Segments = Segments(1 << 4);
+    pub const F: Segments = Segments(1 << 5);
+    pub const G: Segments = Segments(1 << 6);
+    pub const P: Segments = Segments(1 << 7);
+    pub const ALL: Segments = Segments(0xFF);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Segments(bits)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// The pattern for a hex digit from 0x0 to 0xF, or `None` for anything larger.
+    pub fn hex(digit: u8) -> Option<Self> {
+        HEX.get(digit as usize).cloned()
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether every segment in `other` is also lit in `self`.
+    pub const fn contains(self, other: Segments) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Lights or clears the segments in `other`.
+    pub fn set(&mut self, other: Segments, on: bool) {
+        if on {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
+
+    /// The lit segments, in the order `a` to `g` then `p`.
+    pub fn iter(self) -> Iter {
+        Iter {
+            segments: self,
+            next: 0,
+        }
+    }
+}
+
+/// Which output bit, 0 to 7, each segment is wired to on a shift register, port expander or
+/// GPIO port, listed in the order `a` to `g` then `p`.
+///```rust
+///    use eight_segment::{SegmentMap, Segments};
+///
+///    // a on Q7, b on Q6, ... p on Q0
+///    let reversed = SegmentMap::new([7, 6, 5, 4, 3, 2, 1, 0]);
+///    assert_eq!(reversed.to_bits(Segments::A | Segments::P), 0b1000_0001);
+///    assert_eq!(reversed.to_bits(Segments::B), 0b0100_0000);
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentMap([u8; 8]);
+
+impl SegmentMap {
+    /// Segment `a` on bit 0 through `p` on bit 7.
+    pub const IDENTITY: SegmentMap = SegmentMap([0, 1, 2, 3, 4, 5, 6, 7]);
+
+    /// # Panics
+    /// If any bit is above 7.
+    pub const fn new(bits: [u8; 8]) -> Self {
+        let mut i = 0;
+        while i < 8 {
+            assert!(bits[i] < 8, "output bits run from 0 to 7");
+            i += 1;
+        }
+        SegmentMap(bits)
+    }
+
+    /// Moves each segment's bit to the output bit it is wired to.
+    pub fn to_bits(&self, segments: Segments) -> u8 {
+        segments
+            .iter()
+            .fold(0, |bits, segment| bits | 1 << self.0[segment as usize])
+    }
+}
+
+impl Default for SegmentMap {
+    fn default() -> Self {
+        SegmentMap::IDENTITY
+    }
+}
+
+const HEX: [Segments; 16] = [
+    Segments(0x3F), // 0
+    Segments(0x06), // 1
+    Segments(0x5B), // 2
+    Segments(0x4F), // 3
+    Segments(0x66), // 4
+    Segments(0x6D), // 5
+    Segments(0x7D), // 6
+    Segments(0x07), // 7
+    Segments(0x7F), // 8
+    Segments(0x67), // 9
+    Segments(0x77), // A
+    Segments(0x7C), // b
+    Segments(0x58), // c
+    Segments(0x5E), // d
+    Segments(0x79), // E
+    Segments(0x71), // F
+];
+
+impl From<Segment> for Segments {
+    fn from(segment: Segment) -> Self {
+        Segments(1 << segment as u8)
+    }
+}
+
+impl From<u8> for Segments {
+    fn from(bits: u8) -> Self {
+        Segments(bits)
+    }
+}
+
+impl From<Segments> for u8 {
+    fn from(segments: Segments) -> Self {
+        segments.0
+    }
+}
+
+impl IntoIterator for Segments {
+    type Item = Segment;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+/// Iterator over the lit segments of a [`Segments`] value.
+#[derive(Debug, Clone)]
+pub struct Iter {
+    segments: Segments,
+    next: usize,
+}
+
+impl Iterator for Iter {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Segment> {
+        while let Some(&segment) = Segment::ALL.get(self.next) {
+            self.next += 1;
+            if self.segments.contains(segment.into()) {
+                return Some(segment);
+            }
+        }
+        None
+    }
+}
+
+impl BitOr for Segments {
+    type Output = Segments;
+
+    fn bitor(self, rhs: Segments) -> Segments {
+        Segments(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for Segments {
+    type Output = Segments;
+
+    fn bitand(self, rhs: Segments) -> Segments {
+        Segments(self.0 & rhs.0)
+    }
+}
+
+impl BitXor for Segments {
+    type Output = Segments;
+
+    fn bitxor(self, rhs: Segments) -> Segments {
+        Segments(self.0 ^ rhs.0)
+    }
+}
+
+impl Not for Segments {
+    type Output = Segments;
+
+    fn not(self) -> Segments {
+        Segments(!self.0)
+    }
+}
+
+impl BitOrAssign for Segments {
+    fn bitor_assign(&mut self, rhs: Segments) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAndAssign for Segments {
+    fn bitand_assign(&mut self, rhs: Segments) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitXorAssign for Segments {
+    fn bitxor_assign(&mut self, rhs: Segments) {
+        self.0 ^= rhs.0;
+    }
+}
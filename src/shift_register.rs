@@ -0,0 +1,184 @@
+use embedded_hal::digital::{OutputPin, PinState};
+
+use {DigitDisplay, Error, Segment, SegmentMap, SegmentPins, Segments};
+
+/// The segment lines of one digit driven through a 74HC595 shift register, so the display needs
+/// three MCU pins instead of eight.
+///
+/// Hand it to [`EightSegment::new`](crate::EightSegment::new) in place of the segment pins to
+/// get the usual `display`, `set_segments` and `write` methods. The clock and latch pins should
+/// start low.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::{EightSegment, SegmentMap, ShiftRegister};
+///
+///    // SER, SRCLK and RCLK, with segment a on Q0 through p on Q7
+///    let register = ShiftRegister::new(data, clock, latch, SegmentMap::IDENTITY);
+///    let mut eight_segment = EightSegment::new(register, false);
+///    eight_segment.display(0xb, false).unwrap();
+///```
+pub struct ShiftRegister<DATA, CLK, LATCH> {
+    pins: Pins<DATA, CLK, LATCH>,
+    map: SegmentMap,
+    levels: u8,
+}
+
+impl<DATA, CLK, LATCH> ShiftRegister<DATA, CLK, LATCH>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    /// Takes the serial data (SER), shift clock (SRCLK) and latch (RCLK) pins. `map` says which
+    /// output Q0 to Q7 each segment is wired to.
+    pub fn new(data: DATA, clock: CLK, latch: LATCH, map: SegmentMap) -> Self {
+        ShiftRegister {
+            pins: Pins { data, clock, latch },
+            map,
+            levels: 0,
+        }
+    }
+
+    /// Hands back the data, clock and latch pins.
+    pub fn release(self) -> (DATA, CLK, LATCH) {
+        (self.pins.data, self.pins.clock, self.pins.latch)
+    }
+}
+
+impl<DATA, CLK, LATCH> SegmentPins for ShiftRegister<DATA, CLK, LATCH>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    type Error = DATA::Error;
+
+    /// Shifts out all eight outputs, so prefer [`set_pins`](SegmentPins::set_pins).
+    fn set_pin(&mut self, segment: Segment, state: PinState) -> Result<(), Self::Error> {
+        let bit = Segments::from(segment).bits();
+        let level = if state == PinState::High { bit } else { 0 };
+        self.set_pins(level, bit)
+    }
+
+    /// Shifts out all eight outputs, keeping the unmasked ones as they were.
+    fn set_pins(&mut self, levels: u8, mask: u8) -> Result<(), Self::Error> {
+        self.levels = self.levels & !mask | levels & mask;
+        self.pins.shift(self.map.to_bits(self.levels.into()))?;
+        self.pins.latch()
+    }
+}
+
+/// `N` digits, each with its own 74HC595, daisy-chained so that every digit shares the same
+/// three MCU pins.
+///
+/// Position 0 is the register whose serial input is wired to the MCU; each register's QH' output
+/// feeds the next position. Every write shifts the whole chain out again.
+pub struct ShiftRegisterChain<DATA, CLK, LATCH, const N: usize> {
+    /// Whether a segment is lit by driving its output high.
+    pub high_on: bool,
+    pins: Pins<DATA, CLK, LATCH>,
+    map: SegmentMap,
+    frame: [Segments; N],
+}
+
+impl<DATA, CLK, LATCH, const N: usize> ShiftRegisterChain<DATA, CLK, LATCH, N>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    /// Takes the pins of the first register in the chain. Every register must be wired with the
+    /// same `map`.
+    pub fn new(data: DATA, clock: CLK, latch: LATCH, map: SegmentMap, high_on: bool) -> Self {
+        ShiftRegisterChain {
+            high_on,
+            pins: Pins { data, clock, latch },
+            map,
+            frame: [Segments::NONE; N],
+        }
+    }
+
+    /// Hands back the data, clock and latch pins.
+    pub fn release(self) -> (DATA, CLK, LATCH) {
+        (self.pins.data, self.pins.clock, self.pins.latch)
+    }
+
+    fn flush(&mut self) -> Result<(), Error<DATA::Error>> {
+        // The first byte shifted in ends up furthest down the chain.
+        for &segments in self.frame.iter().rev() {
+            let levels = if self.high_on { segments } else { !segments };
+            self.pins.shift(self.map.to_bits(levels))?;
+        }
+        self.pins.latch()?;
+        Ok(())
+    }
+}
+
+impl<DATA, CLK, LATCH, const N: usize> DigitDisplay for ShiftRegisterChain<DATA, CLK, LATCH, N>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    type Error = DATA::Error;
+
+    fn digits(&self) -> usize {
+        N
+    }
+
+    fn write_digit(
+        &mut self,
+        position: usize,
+        segments: Segments,
+    ) -> Result<(), Error<Self::Error>> {
+        let digit = self
+            .frame
+            .get_mut(position)
+            .ok_or(Error::InvalidPosition(position))?;
+        *digit = segments;
+        self.flush()
+    }
+
+    fn write_digits(&mut self, digits: &[Segments]) -> Result<(), Error<Self::Error>> {
+        if digits.len() > N {
+            return Err(Error::InvalidPosition(digits.len() - 1));
+        }
+        self.frame[..digits.len()].copy_from_slice(digits);
+        self.flush()
+    }
+
+    fn blank(&mut self) -> Result<(), Error<Self::Error>> {
+        self.frame = [Segments::NONE; N];
+        self.flush()
+    }
+}
+
+struct Pins<DATA, CLK, LATCH> {
+    data: DATA,
+    clock: CLK,
+    latch: LATCH,
+}
+
+impl<DATA, CLK, LATCH> Pins<DATA, CLK, LATCH>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    /// Clocks `bits` into the shift register, Q7 first.
+    fn shift(&mut self, bits: u8) -> Result<(), DATA::Error> {
+        for bit in (0..8).rev() {
+            self.data.set_state(PinState::from(bits & 1 << bit != 0))?;
+            self.clock.set_high()?;
+            self.clock.set_low()?;
+        }
+        Ok(())
+    }
+
+    /// Copies the shift register to the outputs.
+    fn latch(&mut self) -> Result<(), DATA::Error> {
+        self.latch.set_high()?;
+        self.latch.set_low()
+    }
+}
@@ -0,0 +1,260 @@
+//! Driver for the TM1637, found on the cheap 4-digit clock modules.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin};
+
+use {DigitDisplay, Error, Segments};
+
+const DATA_AUTO_INCREMENT: u8 = 0x40;
+const DATA_FIXED_ADDRESS: u8 = 0x44;
+const ADDRESS: u8 = 0xC0;
+const DISPLAY_CONTROL: u8 = 0x80;
+const DISPLAY_ON: u8 = 0x08;
+
+/// The digit whose `p` bit drives the colon on 4-digit clock modules.
+const COLON_POSITION: usize = 1;
+
+/// How digits are sent to the TM1637.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressMode {
+    /// Send a start address and then every digit in one go. The default.
+    #[default]
+    Auto,
+    /// Send every digit with its own address. Slower, but each write is self-contained.
+    Fixed,
+}
+
+/// A TM1637 driving up to six digits over its two-wire CLK/DIO interface.
+///
+/// On clock modules the colon is wired to the `p` bit of digit 1; [`set_colon`](Tm1637::set_colon)
+/// keeps it separate from whatever is written to that digit.
+///
+/// DIO must be an open-drain pin that can also be read, with a pull-up, because the TM1637 pulls
+/// it low to acknowledge each byte. Most modules already fit pull-ups to both lines.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::tm1637::Tm1637;
+///    use eight_segment::{DigitDisplay, Segments};
+///
+///    let mut display: Tm1637<_, _, _, 4> = Tm1637::new(clk, dio, delay);
+///    display.init().unwrap();
+///    display.set_brightness(3).unwrap();
+///    display.write_digits(&[Segments::hex(1).unwrap(), Segments::hex(2).unwrap()]).unwrap();
+///    display.set_colon(true).unwrap();
+///```
+pub struct Tm1637<CLK, DIO, DELAY, const N: usize = 4> {
+    /// How [`DigitDisplay`] writes are sent.
+    pub address_mode: AddressMode,
+    /// Half the clock period, in microseconds. The RC filters on many modules need 50 or more.
+    pub bit_delay_us: u32,
+    clock: CLK,
+    dio: DIO,
+    delay: DELAY,
+    frame: [Segments; N],
+    colon: bool,
+    brightness: u8,
+    on: bool,
+}
+
+impl<CLK, DIO, DELAY, const N: usize> Tm1637<CLK, DIO, DELAY, N>
+where
+    CLK: OutputPin,
+    DIO: OutputPin<Error = CLK::Error> + InputPin,
+    DELAY: DelayNs,
+{
+    /// Takes the clock and data pins, both of which should start high. Nothing is sent until
+    /// [`init`](Tm1637::init).
+    pub fn new(clock: CLK, dio: DIO, delay: DELAY) -> Self {
+        Tm1637 {
+            address_mode: AddressMode::default(),
+            bit_delay_us: 50,
+            clock,
+            dio,
+            delay,
+            frame: [Segments::NONE; N],
+            colon: false,
+            brightness: 7,
+            on: true,
+        }
+    }
+
+    /// Hands back the clock pin, data pin and delay.
+    pub fn release(self) -> (CLK, DIO, DELAY) {
+        (self.clock, self.dio, self.delay)
+    }
+
+    /// Blanks every digit and turns the display on at full brightness.
+    pub fn init(&mut self) -> Result<(), Error<CLK::Error>> {
+        self.blank()?;
+        self.brightness = 7;
+        self.on = true;
+        self.send_display_control()
+    }
+
+    /// Sets the brightness from 0 (1/16 duty) to 7 (14/16 duty).
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), Error<CLK::Error>> {
+        self.brightness = level.min(7);
+        self.send_display_control()
+    }
+
+    /// Turns the display on or off without losing its contents.
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), Error<CLK::Error>> {
+        self.on = on;
+        self.send_display_control()
+    }
+
+    /// Lights or clears the colon, leaving the digits as they are.
+    pub fn set_colon(&mut self, on: bool) -> Result<(), Error<CLK::Error>> {
+        self.colon = on;
+        if COLON_POSITION < N {
+            self.send_digits(COLON_POSITION, COLON_POSITION + 1)?;
+        }
+        Ok(())
+    }
+
+    fn send_display_control(&mut self) -> Result<(), Error<CLK::Error>> {
+        let on = if self.on { DISPLAY_ON } else { 0 };
+        self.command(&[DISPLAY_CONTROL | on | self.brightness])
+    }
+
+    /// Sends `frame[start..end]`.
+    fn send_digits(&mut self, start: usize, end: usize) -> Result<(), Error<CLK::Error>> {
+        match self.address_mode {
+            AddressMode::Auto => {
+                self.command(&[DATA_AUTO_INCREMENT])?;
+                self.framed(|tm| {
+                    tm.write_byte(ADDRESS | start as u8)?;
+                    for position in start..end {
+                        let bits = tm.digit_bits(position);
+                        tm.write_byte(bits)?;
+                    }
+                    Ok(())
+                })
+            }
+            AddressMode::Fixed => {
+                self.command(&[DATA_FIXED_ADDRESS])?;
+                for position in start..end {
+                    let bits = self.digit_bits(position);
+                    self.command(&[ADDRESS | position as u8, bits])?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn digit_bits(&self, position: usize) -> u8 {
+        let mut segments = self.frame[position];
+        if position == COLON_POSITION && self.colon {
+            segments |= Segments::P;
+        }
+        segments.bits()
+    }
+
+    /// Sends `bytes` between one start and stop condition.
+    fn command(&mut self, bytes: &[u8]) -> Result<(), Error<CLK::Error>> {
+        self.framed(|tm| bytes.iter().try_for_each(|&byte| tm.write_byte(byte)))
+    }
+
+    /// Runs `send` between a start and a stop condition. The stop is sent even if `send` fails,
+    /// so that the next start is seen as one.
+    fn framed<F>(&mut self, send: F) -> Result<(), Error<CLK::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error<CLK::Error>>,
+    {
+        self.start()?;
+        let sent = send(self);
+        let stopped = self.stop();
+        sent.and(stopped)
+    }
+
+    /// Pulls DIO low while CLK is high.
+    fn start(&mut self) -> Result<(), Error<CLK::Error>> {
+        self.dio.set_low()?;
+        self.delay.delay_us(self.bit_delay_us);
+        Ok(())
+    }
+
+    /// Releases DIO while CLK is high.
+    fn stop(&mut self) -> Result<(), Error<CLK::Error>> {
+        self.clock.set_low()?;
+        self.dio.set_low()?;
+        self.delay.delay_us(self.bit_delay_us);
+        self.clock.set_high()?;
+        self.delay.delay_us(self.bit_delay_us);
+        self.dio.set_high()?;
+        self.delay.delay_us(self.bit_delay_us);
+        Ok(())
+    }
+
+    /// Clocks out `byte` least significant bit first, then checks the TM1637 pulls DIO low for
+    /// the ninth clock.
+    fn write_byte(&mut self, byte: u8) -> Result<(), Error<CLK::Error>> {
+        for bit in 0..8 {
+            self.clock.set_low()?;
+            if byte & 1 << bit != 0 {
+                self.dio.set_high()?;
+            } else {
+                self.dio.set_low()?;
+            }
+            self.delay.delay_us(self.bit_delay_us);
+            self.clock.set_high()?;
+            self.delay.delay_us(self.bit_delay_us);
+        }
+
+        self.clock.set_low()?;
+        self.dio.set_high()?;
+        self.delay.delay_us(self.bit_delay_us);
+        self.clock.set_high()?;
+        self.delay.delay_us(self.bit_delay_us);
+        let acknowledged = self.dio.is_low()?;
+        self.clock.set_low()?;
+        if acknowledged {
+            Ok(())
+        } else {
+            Err(Error::NoAcknowledge)
+        }
+    }
+}
+
+impl<CLK, DIO, DELAY, const N: usize> DigitDisplay for Tm1637<CLK, DIO, DELAY, N>
+where
+    CLK: OutputPin,
+    DIO: OutputPin<Error = CLK::Error> + InputPin,
+    DELAY: DelayNs,
+{
+    type Error = CLK::Error;
+
+    fn digits(&self) -> usize {
+        N
+    }
+
+    /// Writes the digit. On digit 1 the colon is lit if either `segments` has `p` or
+    /// [`set_colon`](Tm1637::set_colon) turned it on.
+    fn write_digit(
+        &mut self,
+        position: usize,
+        segments: Segments,
+    ) -> Result<(), Error<Self::Error>> {
+        let digit = self
+            .frame
+            .get_mut(position)
+            .ok_or(Error::InvalidPosition(position))?;
+        *digit = segments;
+        self.send_digits(position, position + 1)
+    }
+
+    fn write_digits(&mut self, digits: &[Segments]) -> Result<(), Error<Self::Error>> {
+        if digits.len() > N {
+            return Err(Error::InvalidPosition(digits.len() - 1));
+        }
+        self.frame[..digits.len()].copy_from_slice(digits);
+        self.send_digits(0, digits.len())
+    }
+
+    fn blank(&mut self) -> Result<(), Error<Self::Error>> {
+        self.frame = [Segments::NONE; N];
+        self.colon = false;
+        self.send_digits(0, N)
+    }
+}
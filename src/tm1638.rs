@@ -0,0 +1,269 @@
+//! Driver for the TM1638, found on the "LED&KEY" boards with eight digits, eight LEDs and eight
+//! buttons.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin};
+
+use {DigitDisplay, Error, Segments};
+
+const DATA_AUTO_INCREMENT: u8 = 0x40;
+const DATA_READ_KEYS: u8 = 0x42;
+const DATA_FIXED_ADDRESS: u8 = 0x44;
+const ADDRESS: u8 = 0xC0;
+const DISPLAY_CONTROL: u8 = 0x80;
+const DISPLAY_ON: u8 = 0x08;
+
+/// A TM1638 driving eight digits and eight LEDs and scanning up to 24 keys over its STB/CLK/DIO
+/// interface.
+///
+/// Each digit's display RAM is followed by a byte whose bit 0 drives one of the discrete LEDs.
+///
+/// DIO must be an open-drain pin that can also be read, with a pull-up, because the TM1638 drives
+/// it when the keys are read.
+///
+/// # Examples
+///```rust,ignore
+///    use eight_segment::tm1638::Tm1638;
+///    use eight_segment::DigitDisplay;
+///
+///    let mut panel = Tm1638::new(stb, clk, dio, delay);
+///    panel.init().unwrap();
+///    panel.display_char(0, 'P', false).unwrap();
+///    let buttons = panel.read_buttons().unwrap();
+///    panel.set_led(0, buttons & 1 != 0).unwrap();
+///```
+pub struct Tm1638<STB, CLK, DIO, DELAY> {
+    /// Half the clock period, in microseconds.
+    pub bit_delay_us: u32,
+    strobe: STB,
+    clock: CLK,
+    dio: DIO,
+    delay: DELAY,
+    frame: [Segments; 8],
+    leds: u8,
+    brightness: u8,
+    on: bool,
+}
+
+impl<STB, CLK, DIO, DELAY> Tm1638<STB, CLK, DIO, DELAY>
+where
+    STB: OutputPin,
+    CLK: OutputPin<Error = STB::Error>,
+    DIO: OutputPin<Error = STB::Error> + InputPin,
+    DELAY: DelayNs,
+{
+    /// Takes the strobe, clock and data pins, all of which should start high. Nothing is sent
+    /// until [`init`](Tm1638::init).
+    pub fn new(strobe: STB, clock: CLK, dio: DIO, delay: DELAY) -> Self {
+        Tm1638 {
+            bit_delay_us: 1,
+            strobe,
+            clock,
+            dio,
+            delay,
+            frame: [Segments::NONE; 8],
+            leds: 0,
+            brightness: 7,
+            on: true,
+        }
+    }
+
+    /// Hands back the strobe pin, clock pin, data pin and delay.
+    pub fn release(self) -> (STB, CLK, DIO, DELAY) {
+        (self.strobe, self.clock, self.dio, self.delay)
+    }
+
+    /// Clears the digits and LEDs and turns the display on at full brightness.
+    pub fn init(&mut self) -> Result<(), Error<STB::Error>> {
+        self.leds = 0;
+        self.blank()?;
+        self.brightness = 7;
+        self.on = true;
+        self.send_display_control()
+    }
+
+    /// Sets the brightness from 0 (1/16 duty) to 7 (14/16 duty).
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), Error<STB::Error>> {
+        self.brightness = level.min(7);
+        self.send_display_control()
+    }
+
+    /// Turns the display and LEDs on or off without losing their contents.
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), Error<STB::Error>> {
+        self.on = on;
+        self.send_display_control()
+    }
+
+    /// Turns the discrete LED `index`, from 0 to 7, on or off.
+    pub fn set_led(&mut self, index: usize, on: bool) -> Result<(), Error<STB::Error>> {
+        if index >= 8 {
+            return Err(Error::InvalidPosition(index));
+        }
+        if on {
+            self.leds |= 1 << index;
+        } else {
+            self.leds &= !(1 << index);
+        }
+        self.command(&[DATA_FIXED_ADDRESS])?;
+        self.command(&[ADDRESS | (2 * index + 1) as u8, on as u8])
+    }
+
+    /// Sets all eight LEDs at once, bit 0 being LED 0.
+    pub fn set_leds(&mut self, leds: u8) -> Result<(), Error<STB::Error>> {
+        self.leds = leds;
+        self.send_all()
+    }
+
+    /// Scans the whole 3×8 key matrix. Bit `8 * k + s` is set while the key between line
+    /// K(k+1) and KS(s+1) is pressed, so K1 is the low byte and K3 the high byte.
+    pub fn read_keys(&mut self) -> Result<u32, Error<STB::Error>> {
+        let bytes = self.read_key_bytes()?;
+        let mut keys = 0;
+        for (i, &byte) in bytes.iter().enumerate() {
+            // Each byte holds two KS columns, with K3 to K1 in bits 0 to 2 and 4 to 6.
+            for line in 0..3 {
+                let k = 2 - line;
+                if byte & 1 << line != 0 {
+                    keys |= 1 << (8 * k + 2 * i);
+                }
+                if byte & 1 << (line + 4) != 0 {
+                    keys |= 1 << (8 * k + 2 * i + 1);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Reads the eight buttons of the LED&KEY board, bit 0 being S1.
+    ///
+    /// The board wires S1 to S4 to KS1, KS3, KS5 and KS7 and S5 to S8 to the even columns, all on
+    /// line K3.
+    pub fn read_buttons(&mut self) -> Result<u8, Error<STB::Error>> {
+        let bytes = self.read_key_bytes()?;
+        Ok(bytes
+            .iter()
+            .enumerate()
+            .fold(0, |buttons, (i, &byte)| buttons | (byte & 0x11) << i))
+    }
+
+    fn read_key_bytes(&mut self) -> Result<[u8; 4], Error<STB::Error>> {
+        self.framed(|tm| {
+            tm.write_byte(DATA_READ_KEYS)?;
+            // Let the TM1638 take over DIO before clocking the key data out.
+            tm.dio.set_high()?;
+            tm.delay.delay_us(2);
+
+            let mut bytes = [0; 4];
+            for byte in bytes.iter_mut() {
+                for bit in 0..8 {
+                    tm.clock.set_low()?;
+                    tm.delay.delay_us(tm.bit_delay_us);
+                    if tm.dio.is_high()? {
+                        *byte |= 1 << bit;
+                    }
+                    tm.clock.set_high()?;
+                    tm.delay.delay_us(tm.bit_delay_us);
+                }
+            }
+            Ok(bytes)
+        })
+    }
+
+    fn send_display_control(&mut self) -> Result<(), Error<STB::Error>> {
+        let on = if self.on { DISPLAY_ON } else { 0 };
+        self.command(&[DISPLAY_CONTROL | on | self.brightness])
+    }
+
+    /// Rewrites all sixteen bytes of display RAM from the frame and LEDs.
+    fn send_all(&mut self) -> Result<(), Error<STB::Error>> {
+        self.command(&[DATA_AUTO_INCREMENT])?;
+        self.framed(|tm| {
+            tm.write_byte(ADDRESS)?;
+            for position in 0..8 {
+                let segments = tm.frame[position].bits();
+                let led = tm.leds >> position & 1;
+                tm.write_byte(segments)?;
+                tm.write_byte(led)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Sends `bytes` with STB held low.
+    fn command(&mut self, bytes: &[u8]) -> Result<(), Error<STB::Error>> {
+        self.framed(|tm| bytes.iter().try_for_each(|&byte| tm.write_byte(byte)))
+    }
+
+    /// Runs `transfer` with STB held low. STB is released even if `transfer` fails, so that the
+    /// next transfer is framed.
+    fn framed<T, F>(&mut self, transfer: F) -> Result<T, Error<STB::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<T, Error<STB::Error>>,
+    {
+        self.strobe.set_low()?;
+        let transferred = transfer(self);
+        let released = self.strobe.set_high();
+        let value = transferred?;
+        released?;
+        Ok(value)
+    }
+
+    /// Clocks out `byte` least significant bit first.
+    fn write_byte(&mut self, byte: u8) -> Result<(), Error<STB::Error>> {
+        for bit in 0..8 {
+            self.clock.set_low()?;
+            if byte & 1 << bit != 0 {
+                self.dio.set_high()?;
+            } else {
+                self.dio.set_low()?;
+            }
+            self.delay.delay_us(self.bit_delay_us);
+            self.clock.set_high()?;
+            self.delay.delay_us(self.bit_delay_us);
+        }
+        Ok(())
+    }
+}
+
+impl<STB, CLK, DIO, DELAY> DigitDisplay for Tm1638<STB, CLK, DIO, DELAY>
+where
+    STB: OutputPin,
+    CLK: OutputPin<Error = STB::Error>,
+    DIO: OutputPin<Error = STB::Error> + InputPin,
+    DELAY: DelayNs,
+{
+    type Error = STB::Error;
+
+    fn digits(&self) -> usize {
+        8
+    }
+
+    fn write_digit(
+        &mut self,
+        position: usize,
+        segments: Segments,
+    ) -> Result<(), Error<Self::Error>> {
+        let digit = self
+            .frame
+            .get_mut(position)
+            .ok_or(Error::InvalidPosition(position))?;
+        *digit = segments;
+        self.command(&[DATA_FIXED_ADDRESS])?;
+        self.command(&[ADDRESS | (2 * position) as u8, segments.bits()])
+    }
+
+    /// Writes the digits along with the LEDs in one transfer.
+    fn write_digits(&mut self, digits: &[Segments]) -> Result<(), Error<Self::Error>> {
+        if digits.len() > 8 {
+            return Err(Error::InvalidPosition(digits.len() - 1));
+        }
+        self.frame[..digits.len()].copy_from_slice(digits);
+        self.send_all()
+    }
+
+    /// Clears the digits, leaving the LEDs as they are.
+    fn blank(&mut self) -> Result<(), Error<Self::Error>> {
+        self.frame = [Segments::NONE; 8];
+        self.send_all()
+    }
+}
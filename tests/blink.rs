@@ -0,0 +1,77 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+mod common;
+
+use eight_segment::{Blink, Heartbeat, Segments};
+
+use common::{lit, mock_display, pins};
+
+#[test]
+fn whole_display_blinks_on_then_off() {
+    let levels = pins(false);
+    let mut blink = Blink::new(mock_display(&levels, true), 300, 200);
+    blink.display_mut().display(0x4, true).unwrap();
+    let four = blink.display().current();
+
+    for &(now, shown) in &[
+        (1000, four),
+        (1299, four),
+        (1300, Segments::NONE),
+        (1499, Segments::NONE),
+        (1500, four),
+    ] {
+        blink.tick(now).unwrap();
+        assert_eq!(lit(&levels, true), shown, "now = {}", now);
+    }
+    assert_eq!(blink.display().current(), four);
+
+    blink.display_mut().display(0x1, false).unwrap();
+    blink.tick(1800).unwrap();
+    assert_eq!(lit(&levels, true), Segments::NONE);
+    blink.stop().unwrap();
+    assert_eq!(lit(&levels, true), Segments::B | Segments::C);
+}
+
+#[test]
+fn chosen_segments_blink_across_timer_wrap() {
+    let levels = pins(false);
+    let mut blink = Blink::new(mock_display(&levels, false), 100, 100);
+    blink.segments = Segments::P;
+    blink.display_mut().display(0x7, true).unwrap();
+    let seven = Segments::A | Segments::B | Segments::C;
+
+    blink.tick(u32::MAX - 49).unwrap();
+    assert_eq!(lit(&levels, false), seven | Segments::P);
+    blink.tick(60).unwrap();
+    assert_eq!(lit(&levels, false), seven);
+
+    let display = blink.release();
+    assert_eq!(display.current(), seven | Segments::P);
+}
+
+#[test]
+fn heartbeat_pulses_only_the_dot() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true);
+    let mut heartbeat = Heartbeat::new(1000);
+    display.display(0x3, false).unwrap();
+    let three = display.current();
+
+    for &(now, dp) in &[
+        (0, true),
+        (499, true),
+        (500, false),
+        (999, false),
+        (1000, true),
+    ] {
+        heartbeat.tick(&mut display, now).unwrap();
+        assert_eq!(
+            lit(&levels, true).contains(Segments::P),
+            dp,
+            "now = {}",
+            now
+        );
+        assert_eq!(lit(&levels, true) & !Segments::P, three);
+    }
+}
@@ -0,0 +1,168 @@
+extern crate eight_segment;
+
+use std::fmt::Write;
+
+use eight_segment::{font, DigitBuffer, Radix, Segments};
+
+fn glyphs(text: &str) -> Vec<Segments> {
+    text.chars().map(|c| font::glyph(c).unwrap()).collect()
+}
+
+#[test]
+fn decimal_points_merge_into_the_previous_digit() {
+    let mut buffer = DigitBuffer::<4>::new();
+    write!(buffer, "{:.1}", 21.5).unwrap();
+    let mut expected = glyphs("215");
+    expected[1] |= Segments::P;
+    assert_eq!(buffer.digits(), &expected[..]);
+
+    let mut right = [Segments::NONE; 4];
+    right[1..].copy_from_slice(&expected);
+    assert_eq!(buffer.right_aligned(), right);
+
+    buffer.clear();
+    write!(buffer, ".1..").unwrap();
+    assert_eq!(
+        buffer.digits(),
+        &[
+            Segments::P,
+            Segments::hex(1).unwrap() | Segments::P,
+            Segments::P
+        ]
+    );
+}
+
+#[test]
+fn overflow_and_unsupported_characters_fail_the_write() {
+    let mut buffer = DigitBuffer::<4>::new();
+    assert!(write!(buffer, "{:5}", 42).is_err());
+    assert!(buffer.overflowed());
+    assert_eq!(buffer.len(), 4);
+
+    buffer.clear();
+    assert!(write!(buffer, "OK").is_err());
+    assert!(!buffer.overflowed());
+    assert_eq!(buffer.digits(), &glyphs("O")[..]);
+}
+
+fn digits(text: &str) -> Vec<Segments> {
+    text.chars()
+        .map(|c| match c {
+            '-' => Segments::G,
+            ' ' => Segments::NONE,
+            _ => Segments::hex(c.to_digit(16).unwrap() as u8).unwrap(),
+        })
+        .collect()
+}
+
+fn set_i32(value: i32, radix: Radix, leading_zeros: bool) -> DigitBuffer<4> {
+    let mut buffer = DigitBuffer::new();
+    buffer.set_i32(value, radix, leading_zeros);
+    buffer
+}
+
+#[test]
+fn numbers_are_right_aligned_in_any_radix() {
+    let mut buffer = DigitBuffer::<4>::new();
+    buffer.set_u32(42, Radix::Decimal, false);
+    let mut expected = vec![Segments::NONE; 2];
+    expected.extend(digits("42"));
+    assert_eq!(buffer.digits(), &expected[..]);
+
+    buffer.set_u32(0xBEEF, Radix::Hexadecimal, false);
+    assert_eq!(buffer.digits(), &digits("BEEF")[..]);
+    buffer.set_u32(5, Radix::Binary, true);
+    assert_eq!(buffer.digits(), &digits("0101")[..]);
+    buffer.set_u32(0o17, Radix::Octal, true);
+    assert_eq!(buffer.digits(), &digits("0017")[..]);
+    buffer.set_u32(0, Radix::Decimal, false);
+    assert_eq!(buffer.digits()[3], Segments::hex(0).unwrap());
+    assert!(!buffer.overflowed());
+}
+
+#[test]
+fn negative_numbers_use_a_minus_sign() {
+    let mut expected = vec![Segments::NONE];
+    expected.extend(digits("-12"));
+    assert_eq!(set_i32(-12, Radix::Decimal, false).digits(), &expected[..]);
+    assert_eq!(
+        set_i32(-12, Radix::Decimal, true).digits(),
+        &digits("-012")[..]
+    );
+    assert_eq!(
+        set_i32(-999, Radix::Decimal, false).digits(),
+        &digits("-999")[..]
+    );
+    assert_eq!(
+        set_i32(i32::MIN, Radix::Hexadecimal, false).digits(),
+        &digits("----")[..]
+    );
+}
+
+#[test]
+fn numbers_that_do_not_fit_show_dashes() {
+    for &(value, radix) in &[
+        (-1000, Radix::Decimal),
+        (10_000, Radix::Decimal),
+        (16, Radix::Binary),
+    ] {
+        let buffer = set_i32(value, radix, false);
+        assert!(buffer.overflowed(), "{} in {:?}", value, radix);
+        assert_eq!(buffer.digits(), &digits("----")[..]);
+    }
+}
+
+fn with_point(mut digits: Vec<Segments>, position: usize) -> Vec<Segments> {
+    digits[position] |= Segments::P;
+    digits
+}
+
+#[test]
+fn fixed_point_lights_the_decimal_point() {
+    let mut buffer = DigitBuffer::<4>::new();
+    buffer.set_fixed(2345, 2);
+    assert_eq!(buffer.digits(), &with_point(digits("2345"), 1)[..]);
+
+    buffer.set_fixed(-5, 2);
+    assert_eq!(buffer.digits(), &with_point(digits("-005"), 1)[..]);
+
+    buffer.set_fixed(3300, 3);
+    assert_eq!(buffer.digits(), &with_point(digits("3300"), 0)[..]);
+
+    buffer.set_fixed(12, 0);
+    assert_eq!(buffer.digits()[2..], digits("12")[..]);
+    assert!(!buffer.overflowed());
+
+    buffer.set_fixed(-1234, 1);
+    assert!(buffer.overflowed());
+    buffer.set_fixed(1, 4);
+    assert!(buffer.overflowed());
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn floats_use_as_many_decimals_as_fit() {
+    let mut buffer = DigitBuffer::<4>::new();
+    buffer.set_f32(1.23456, 3);
+    assert_eq!(buffer.digits(), &with_point(digits("1235"), 0)[..]);
+
+    buffer.set_f32(23.456, 3);
+    assert_eq!(buffer.digits(), &with_point(digits("2346"), 1)[..]);
+
+    buffer.set_f32(-9.996, 2);
+    assert_eq!(buffer.digits(), &with_point(digits("-100"), 2)[..]);
+
+    buffer.set_f32(0.49999997, 0);
+    assert_eq!(buffer.digits(), &digits("   0")[..]);
+    buffer.set_f32(-0.5, 0);
+    assert_eq!(buffer.digits(), &digits("  -1")[..]);
+
+    buffer.set_f32(1.0, 40);
+    assert_eq!(buffer.digits(), &with_point(digits("1000"), 0)[..]);
+    assert!(!buffer.overflowed());
+
+    buffer.set_f32(99999.0, 2);
+    assert!(buffer.overflowed());
+    buffer.set_f32(f32::NAN, 2);
+    assert_eq!(buffer.digits(), &digits("----")[..]);
+}
@@ -0,0 +1,69 @@
+extern crate eight_segment;
+
+use eight_segment::{ClockFields, ClockFormat, ColonStyle, Segments};
+
+fn digits(text: &str) -> [Segments; 4] {
+    let mut digits = [Segments::NONE; 4];
+    for (digit, c) in digits.iter_mut().zip(text.chars()) {
+        if let Some(value) = c.to_digit(10) {
+            *digit = Segments::hex(value as u8).unwrap();
+        }
+    }
+    digits
+}
+
+#[test]
+fn colon_blinks_on_even_seconds() {
+    let format = ClockFormat::default();
+    let clock = format.render(9, 5, 42);
+    let mut expected = digits("0905");
+    expected[1] |= Segments::P;
+    assert_eq!(clock.digits, expected);
+    assert!(clock.colon);
+
+    let clock = format.render(9, 5, 43);
+    assert_eq!(clock.digits, digits("0905"));
+    assert!(!clock.colon);
+
+    let steady = ClockFormat {
+        blink_colon: false,
+        colon: ColonStyle::Separate,
+        ..format
+    };
+    let clock = steady.render(9, 5, 43);
+    assert_eq!(clock.digits, digits("0905"));
+    assert!(clock.colon);
+}
+
+#[test]
+fn twelve_hour_mode_marks_pm_and_drops_the_leading_zero() {
+    let format = ClockFormat {
+        twelve_hour: true,
+        leading_zero: false,
+        blink_colon: false,
+        colon: ColonStyle::Separate,
+        ..ClockFormat::default()
+    };
+
+    let clock = format.render(13, 7, 0);
+    let mut expected = digits(" 107");
+    expected[3] |= Segments::P;
+    assert_eq!(clock.digits, expected);
+    assert!(clock.pm);
+
+    assert_eq!(format.render(0, 30, 0).digits, digits("1230"));
+    assert!(!format.render(11, 59, 0).pm);
+}
+
+#[test]
+fn minutes_and_seconds() {
+    let format = ClockFormat {
+        fields: ClockFields::MinutesSeconds,
+        colon: ColonStyle::Separate,
+        twelve_hour: true,
+        ..ClockFormat::default()
+    };
+    let clock = format.render(23, 4, 59);
+    assert_eq!(clock.digits, digits("0459"));
+    assert!(!clock.colon);
+}
@@ -0,0 +1,98 @@
+//! Mock pins shared by the integration tests.
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::convert::Infallible;
+
+use eight_segment::{EightSegment, Segments};
+use embedded_hal::digital::{ErrorKind, ErrorType, OutputPin};
+use embedded_hal::i2c::{self, I2c, Operation};
+
+/// A pin that records whether it is high.
+pub struct MockPin<'a>(pub &'a Cell<bool>);
+
+impl<'a> ErrorType for MockPin<'a> {
+    type Error = Infallible;
+}
+
+impl<'a> OutputPin for MockPin<'a> {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.set(true);
+        Ok(())
+    }
+}
+
+/// A pin whose writes always fail.
+pub struct BrokenPin;
+
+impl ErrorType for BrokenPin {
+    type Error = ErrorKind;
+}
+
+impl OutputPin for BrokenPin {
+    fn set_low(&mut self) -> Result<(), ErrorKind> {
+        Err(ErrorKind::Other)
+    }
+
+    fn set_high(&mut self) -> Result<(), ErrorKind> {
+        Err(ErrorKind::Other)
+    }
+}
+
+pub fn mock_display(levels: &[Cell<bool>; 8], high_on: bool) -> EightSegment<[MockPin<'_>; 8]> {
+    let pins = [
+        MockPin(&levels[0]),
+        MockPin(&levels[1]),
+        MockPin(&levels[2]),
+        MockPin(&levels[3]),
+        MockPin(&levels[4]),
+        MockPin(&levels[5]),
+        MockPin(&levels[6]),
+        MockPin(&levels[7]),
+    ];
+    EightSegment::new(pins, high_on)
+}
+
+/// The segments currently lit, decoded from the pin levels for the given polarity.
+pub fn lit(levels: &[Cell<bool>; 8], high_on: bool) -> Segments {
+    let mut bits = 0;
+    for (i, level) in levels.iter().enumerate() {
+        if level.get() == high_on {
+            bits |= 1 << i;
+        }
+    }
+    Segments::from_bits(bits)
+}
+
+pub fn pins<const N: usize>(initial: bool) -> [Cell<bool>; N] {
+    std::array::from_fn(|_| Cell::new(initial))
+}
+
+/// Records the address and bytes of each write.
+#[derive(Default)]
+pub struct MockI2c(pub Vec<(u8, Vec<u8>)>);
+
+impl i2c::ErrorType for MockI2c {
+    type Error = Infallible;
+}
+
+impl I2c for MockI2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Infallible> {
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => self.0.push((address, bytes.to_vec())),
+                Operation::Read(_) => panic!("MockI2c only records writes"),
+            }
+        }
+        Ok(())
+    }
+}
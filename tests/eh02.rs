@@ -0,0 +1,64 @@
+#![cfg(feature = "eh02")]
+
+extern crate eight_segment;
+extern crate embedded_hal_02;
+
+use std::cell::Cell;
+
+use eight_segment::eh02::{Eh02Error, Eh02Pin};
+use eight_segment::{EightSegment, Error, Segments};
+use embedded_hal_02::digital::v2::OutputPin;
+
+/// An embedded-hal 0.2 pin that records whether it is high, or fails every write.
+struct LegacyPin<'a>(Option<&'a Cell<bool>>);
+
+impl<'a> OutputPin for LegacyPin<'a> {
+    type Error = &'static str;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.ok_or("broken").map(|level| level.set(false))
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.ok_or("broken").map(|level| level.set(true))
+    }
+}
+
+#[test]
+fn legacy_pins_drive_segments() {
+    let levels: Vec<Cell<bool>> = (0..8).map(|_| Cell::new(true)).collect();
+    let pins = [
+        Eh02Pin::new(LegacyPin(Some(&levels[0]))),
+        Eh02Pin::new(LegacyPin(Some(&levels[1]))),
+        Eh02Pin::new(LegacyPin(Some(&levels[2]))),
+        Eh02Pin::new(LegacyPin(Some(&levels[3]))),
+        Eh02Pin::new(LegacyPin(Some(&levels[4]))),
+        Eh02Pin::new(LegacyPin(Some(&levels[5]))),
+        Eh02Pin::new(LegacyPin(Some(&levels[6]))),
+        Eh02Pin::new(LegacyPin(Some(&levels[7]))),
+    ];
+    let mut display = EightSegment::new(pins, false);
+    display.write(Segments::A | Segments::P).unwrap();
+
+    let low: Vec<bool> = levels.iter().map(|level| !level.get()).collect();
+    assert_eq!(low, [true, false, false, false, false, false, false, true]);
+}
+
+#[test]
+fn legacy_pin_errors_are_wrapped() {
+    let mut display = EightSegment::new(
+        [
+            LegacyPin(None),
+            LegacyPin(None),
+            LegacyPin(None),
+            LegacyPin(None),
+            LegacyPin(None),
+            LegacyPin(None),
+            LegacyPin(None),
+            LegacyPin(None),
+        ]
+        .map(Eh02Pin::new),
+        true,
+    );
+    assert_eq!(display.blank(), Err(Error::Pin(Eh02Error("broken"))));
+}
@@ -0,0 +1,214 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+mod common;
+
+use eight_segment::{EightSegment, Error, OutOfRange, Segments};
+use embedded_hal::digital::ErrorKind;
+
+use common::{lit, mock_display, pins, BrokenPin, MockPin};
+
+#[test]
+fn blank_turns_every_segment_off() {
+    for &high_on in &[false, true] {
+        let levels = pins(high_on);
+        mock_display(&levels, high_on).blank().unwrap();
+        assert_eq!(
+            lit(&levels, high_on),
+            Segments::NONE,
+            "high_on = {}",
+            high_on
+        );
+        assert!(levels.iter().all(|level| level.get() != high_on));
+    }
+}
+
+#[test]
+fn all_on_lights_every_segment() {
+    for &high_on in &[false, true] {
+        let levels = pins(!high_on);
+        mock_display(&levels, high_on).all_on().unwrap();
+        assert_eq!(
+            lit(&levels, high_on),
+            Segments::ALL,
+            "high_on = {}",
+            high_on
+        );
+    }
+}
+
+#[test]
+fn display_drives_hex_patterns() {
+    for &high_on in &[false, true] {
+        let levels = pins(false);
+        let mut display = mock_display(&levels, high_on);
+
+        display.display(0x1, false).unwrap();
+        assert_eq!(lit(&levels, high_on), Segments::B | Segments::C);
+
+        display.display(0x7, true).unwrap();
+        assert_eq!(
+            lit(&levels, high_on),
+            Segments::A | Segments::B | Segments::C | Segments::P
+        );
+    }
+}
+
+#[test]
+fn set_segments_matches_write() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true);
+    display
+        .set_segments(true, false, false, true, false, false, true, false)
+        .unwrap();
+    assert_eq!(lit(&levels, true), Segments::A | Segments::D | Segments::G);
+}
+
+#[test]
+fn try_display_rejects_out_of_range_counts() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true);
+    display.display(0x8, true).unwrap();
+    assert_eq!(
+        display.try_display(0x10, false),
+        Err(Error::InvalidDigit(0x10))
+    );
+    assert_eq!(lit(&levels, true), Segments::ALL);
+}
+
+#[test]
+fn display_applies_out_of_range_policy() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, false);
+
+    display.display(0x10, true).unwrap();
+    assert_eq!(lit(&levels, false), Segments::G | Segments::P);
+
+    display.out_of_range = OutOfRange::Blank;
+    display.display(0x10, true).unwrap();
+    assert_eq!(lit(&levels, false), Segments::NONE);
+
+    display.out_of_range = OutOfRange::AllOn;
+    display.display(0x10, false).unwrap();
+    assert_eq!(lit(&levels, false), !Segments::P);
+}
+
+#[test]
+fn pin_errors_are_returned() {
+    let levels = pins::<8>(false);
+    let pins = (
+        MockPin(&levels[0]),
+        MockPin(&levels[1]),
+        MockPin(&levels[2]),
+        MockPin(&levels[3]),
+        MockPin(&levels[4]),
+        MockPin(&levels[5]),
+        MockPin(&levels[6]),
+        MockPin(&levels[7]),
+    );
+    assert!(EightSegment::new(pins, true).all_on().is_ok());
+
+    let mut display = EightSegment::new(
+        [
+            BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin,
+        ],
+        true,
+    );
+    assert_eq!(display.blank(), Err(Error::Pin(ErrorKind::Other)));
+}
+
+#[test]
+fn display_char_uses_the_font() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true);
+
+    display.display_char('H', true).unwrap();
+    assert_eq!(
+        lit(&levels, true),
+        Segments::B | Segments::C | Segments::E | Segments::F | Segments::G | Segments::P
+    );
+
+    assert_eq!(
+        display.display_char('K', false),
+        Err(Error::UnsupportedChar('K'))
+    );
+    assert!(lit(&levels, true).contains(Segments::P));
+}
+
+#[test]
+fn software_pwm_lights_for_the_brightness_fraction() {
+    for &high_on in &[false, true] {
+        let levels = pins(false);
+        let mut display = mock_display(&levels, high_on);
+        display.display(0x3, true).unwrap();
+        display.set_brightness(4).unwrap();
+
+        let mut lit_ticks = 0;
+        for _ in 0..30 {
+            display.tick().unwrap();
+            let shown = lit(&levels, high_on);
+            assert!(shown == Segments::NONE || shown == display.current());
+            if shown != Segments::NONE {
+                lit_ticks += 1;
+            }
+        }
+        assert_eq!(lit_ticks, 8, "high_on = {}", high_on);
+
+        display.set_brightness(0).unwrap();
+        display.tick().unwrap();
+        assert_eq!(lit(&levels, high_on), Segments::NONE);
+        assert_eq!(display.current(), Segments::hex(0x3).unwrap() | Segments::P);
+    }
+}
+
+#[test]
+fn dp_changes_keep_the_digit() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, false);
+    display.display(0x1, false).unwrap();
+
+    display.set_dp(true).unwrap();
+    assert_eq!(lit(&levels, false), Segments::B | Segments::C | Segments::P);
+    display.toggle_dp().unwrap();
+    assert_eq!(lit(&levels, false), Segments::B | Segments::C);
+    display.toggle_dp().unwrap();
+    display.set_dp(true).unwrap();
+    assert_eq!(display.current(), Segments::B | Segments::C | Segments::P);
+}
+
+#[test]
+fn set_segments_and_display_return_pin_errors() {
+    let mut display = EightSegment::new(
+        [
+            BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin,
+        ],
+        false,
+    );
+    assert_eq!(
+        display.set_segments(true, true, false, false, false, false, false, false),
+        Err(Error::Pin(ErrorKind::Other))
+    );
+    assert_eq!(
+        display.display(0x1, false),
+        Err(Error::Pin(ErrorKind::Other))
+    );
+}
+
+#[test]
+fn try_display_matches_display_for_hex_digits() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true);
+    assert_eq!(display.out_of_range, OutOfRange::ErrorGlyph);
+    for count in 0..0x10 {
+        display.display(count, count % 2 == 0).unwrap();
+        let shown = display.current();
+        display.blank().unwrap();
+        display.try_display(count, count % 2 == 0).unwrap();
+        assert_eq!(display.current(), shown, "{:X}", count);
+        assert_eq!(lit(&levels, true), shown);
+    }
+    assert_eq!(
+        display.try_display(0xFF, true),
+        Err(Error::InvalidDigit(0xFF))
+    );
+}
@@ -0,0 +1,34 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+mod common;
+
+use eight_segment::expander::{Mcp23017, Pcf8574, Port};
+use eight_segment::{EightSegment, SegmentMap, Segments};
+
+use common::MockI2c;
+
+#[test]
+fn pcf8574_writes_one_byte_per_update() {
+    let pins = Pcf8574::new(MockI2c::default(), 0x38, SegmentMap::IDENTITY);
+    let mut display = EightSegment::new(pins, false);
+    display.display(0x1, false).unwrap();
+    display.blank().unwrap();
+    assert_eq!(
+        display.release().release().0,
+        [(0x38, vec![0b1111_1001]), (0x38, vec![0xFF])]
+    );
+}
+
+#[test]
+fn mcp23017_writes_the_port_latch() {
+    let map = SegmentMap::new([7, 6, 5, 4, 3, 2, 1, 0]);
+    let mut pins = Mcp23017::new(MockI2c::default(), 0x21, Port::B, map);
+    pins.init().unwrap();
+    let mut display = EightSegment::new(pins, true);
+    display.write(Segments::A | Segments::P).unwrap();
+    assert_eq!(
+        display.release().release().0,
+        [(0x21, vec![0x01, 0x00]), (0x21, vec![0x15, 0b1000_0001])]
+    );
+}
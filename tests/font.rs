@@ -0,0 +1,36 @@
+extern crate eight_segment;
+
+use eight_segment::{font, Segments};
+
+#[test]
+fn hex_digits_match_the_font() {
+    for (digit, c) in "0123456789AbcdEF".chars().enumerate() {
+        assert_eq!(Segments::hex(digit as u8), font::glyph(c), "{}", c);
+    }
+}
+
+#[test]
+fn status_words_are_drawable() {
+    for word in &[
+        "Err",
+        "HI",
+        "Lo",
+        "PASS",
+        "run",
+        "donE",
+        "t=23\u{b0}",
+        "Y_-?",
+    ] {
+        assert!(word.chars().all(|c| font::glyph(c).is_some()), "{}", word);
+    }
+    for &c in &['K', 'm', 'W', 'x', '\n', '\u{e9}'] {
+        assert_eq!(font::glyph(c), None, "{:?}", c);
+    }
+}
+
+#[test]
+fn single_case_letters_share_a_glyph() {
+    for &(upper, lower) in &[('B', 'b'), ('D', 'd'), ('R', 'r')] {
+        assert_eq!(font::glyph(upper), font::glyph(lower), "{}", upper);
+    }
+}
@@ -0,0 +1,72 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+mod common;
+
+use eight_segment::ht16k33::{BlinkRate, Ht16k33, Indicator, DEFAULT_ADDRESS};
+use eight_segment::{DigitDisplay, Error, Segments};
+
+use common::MockI2c;
+
+#[test]
+fn init_sets_up_the_chip() {
+    let mut display = Ht16k33::new(MockI2c::default(), DEFAULT_ADDRESS);
+    display.init().unwrap();
+    let writes: Vec<Vec<u8>> = display
+        .release()
+        .0
+        .into_iter()
+        .map(|(_, bytes)| bytes)
+        .collect();
+    assert_eq!(
+        writes,
+        [
+            vec![0x21],
+            vec![0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0xEF],
+            vec![0x81]
+        ]
+    );
+}
+
+#[test]
+fn digits_skip_the_colon_address() {
+    let mut display = Ht16k33::new(MockI2c::default(), 0x71);
+    display.display(2, 0x1, true).unwrap();
+    display.set_colon(true).unwrap();
+    display
+        .set_indicator(Indicator::DecimalPoint, true)
+        .unwrap();
+    display
+        .set_indicator(Indicator::LeftColonLower, true)
+        .unwrap();
+    display
+        .set_indicator(Indicator::LeftColonUpper, true)
+        .unwrap();
+    display.set_colon(false).unwrap();
+    display.write_digits(&[Segments::A, Segments::G]).unwrap();
+    display.set_blink_rate(BlinkRate::HalfHz).unwrap();
+    display.set_dimming(20).unwrap();
+    assert_eq!(
+        display.write_digit(4, Segments::A),
+        Err(Error::InvalidPosition(4))
+    );
+
+    let writes = display.release().0;
+    assert!(writes.iter().all(|&(address, _)| address == 0x71));
+    let writes: Vec<Vec<u8>> = writes.into_iter().map(|(_, bytes)| bytes).collect();
+    assert_eq!(
+        writes,
+        [
+            vec![0x06, 0x86],
+            vec![0x04, 0x02],
+            vec![0x04, 0x12],
+            vec![0x04, 0x16],
+            vec![0x04, 0x1E],
+            vec![0x04, 0x1C],
+            vec![0x00, 0x01, 0, 0x40, 0, 0x1C, 0, 0x86, 0, 0],
+            vec![0x87],
+            vec![0xEF],
+        ]
+    );
+}
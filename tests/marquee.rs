@@ -0,0 +1,97 @@
+extern crate eight_segment;
+
+use std::convert::Infallible;
+
+use eight_segment::{font, DigitDisplay, Error, Marquee, ScrollDirection, Segments};
+
+/// A display that keeps the digits written and counts the writes.
+struct MockDisplay {
+    digits: [Segments; 4],
+    writes: usize,
+}
+
+impl DigitDisplay for MockDisplay {
+    type Error = Infallible;
+
+    fn digits(&self) -> usize {
+        4
+    }
+
+    fn write_digit(
+        &mut self,
+        position: usize,
+        segments: Segments,
+    ) -> Result<(), Error<Infallible>> {
+        self.digits[position] = segments;
+        self.writes += 1;
+        Ok(())
+    }
+}
+
+fn showing(text: &str) -> [Segments; 4] {
+    let mut digits = [Segments::NONE; 4];
+    for (digit, c) in digits.iter_mut().zip(text.chars()) {
+        *digit = font::glyph(c).unwrap();
+    }
+    digits
+}
+
+#[test]
+fn scrolls_left_with_pauses_and_loops() {
+    let mut display = MockDisplay {
+        digits: [Segments::NONE; 4],
+        writes: 0,
+    };
+    let mut marquee = Marquee::<4>::new("HELLO");
+    marquee.step_ms = 100;
+    marquee.pause_ms = 500;
+
+    for &(now, text) in &[
+        (1000, "HELL"),
+        (1499, "HELL"),
+        (1500, "ELLO"),
+        (1999, "ELLO"),
+        (2000, "HELL"),
+    ] {
+        marquee.tick(&mut display, now).unwrap();
+        assert_eq!(display.digits, showing(text), "now = {}", now);
+    }
+    assert_eq!(display.writes, 3 * 4);
+    assert!(!marquee.finished());
+}
+
+#[test]
+fn one_shot_scrolling_right_finishes() {
+    let mut display = MockDisplay {
+        digits: [Segments::NONE; 4],
+        writes: 0,
+    };
+    let mut marquee = Marquee::<4>::new("duE 1.0");
+    marquee.direction = ScrollDirection::Right;
+    marquee.looping = false;
+    marquee.step_ms = 100;
+    marquee.pause_ms = 150;
+
+    let mut end = showing("E 10");
+    end[2] |= Segments::P;
+    marquee.tick(&mut display, u32::MAX - 50).unwrap();
+    assert_eq!(display.digits, end);
+    for now in 0..300 {
+        marquee.tick(&mut display, now).unwrap();
+    }
+    assert_eq!(display.digits, showing("duE "));
+    assert!(!marquee.finished());
+    marquee.tick(&mut display, 349).unwrap();
+    assert!(marquee.finished());
+
+    marquee.set_text("Hi");
+    marquee.tick(&mut display, 400).unwrap();
+    assert_eq!(display.digits, showing("Hi  "));
+    assert_eq!(marquee.frame(), showing("Hi  "));
+    assert!(!marquee.finished());
+    marquee.tick(&mut display, 549).unwrap();
+    assert!(!marquee.finished());
+    marquee.tick(&mut display, 550).unwrap();
+    assert!(marquee.finished());
+    assert_eq!(display.digits, showing("Hi  "));
+}
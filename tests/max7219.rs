@@ -0,0 +1,84 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+use std::convert::Infallible;
+
+use eight_segment::max7219::Max7219;
+use eight_segment::{DigitDisplay, Error, Segments};
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+/// Records the bytes of each transaction.
+#[derive(Default)]
+struct MockSpi(Vec<Vec<u8>>);
+
+impl ErrorType for MockSpi {
+    type Error = Infallible;
+}
+
+impl SpiDevice for MockSpi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        let mut bytes = Vec::new();
+        for operation in operations {
+            match operation {
+                Operation::Write(words) => bytes.extend_from_slice(words),
+                _ => panic!("the MAX7219 is write-only"),
+            }
+        }
+        self.0.push(bytes);
+        Ok(())
+    }
+}
+
+#[test]
+fn raw_segments_use_the_no_decode_bit_order() {
+    let mut display: Max7219<_, 1> = Max7219::new(MockSpi::default());
+    display.write_digit(0, Segments::A | Segments::P).unwrap();
+    display.display(7, 0x1, false).unwrap();
+    display.display_char(3, '-', false).unwrap();
+    assert_eq!(
+        display.release().0,
+        [
+            vec![0x08, 0b1100_0000],
+            vec![0x01, 0b0011_0000],
+            vec![0x05, 0b0000_0001]
+        ]
+    );
+}
+
+#[test]
+fn chained_chips_get_no_ops() {
+    let mut display: Max7219<_, 2> = Max7219::new(MockSpi::default());
+    display.write_digit(9, Segments::G).unwrap();
+    display.set_intensity(20).unwrap();
+    display.write_code_b(0, 0xC, true).unwrap();
+    assert_eq!(
+        display.write_code_b(0, 0x10, false),
+        Err(Error::InvalidDigit(0x10))
+    );
+    assert_eq!(
+        display.write_digit(16, Segments::G),
+        Err(Error::InvalidPosition(16))
+    );
+    assert_eq!(
+        display.release().0,
+        [
+            // chip 1 is furthest from the MCU, so its word goes first
+            vec![0x07, 0x01, 0x00, 0x00],
+            vec![0x0A, 0x0F, 0x0A, 0x0F],
+            vec![0x00, 0x00, 0x08, 0x8C],
+        ]
+    );
+}
+
+#[test]
+fn write_digits_batches_each_register() {
+    let mut display: Max7219<_, 2> = Max7219::new(MockSpi::default());
+    let mut digits = [Segments::NONE; 9];
+    digits[0] = Segments::A;
+    digits[8] = Segments::B;
+    display.write_digits(&digits).unwrap();
+    let transfers = display.release().0;
+    assert_eq!(transfers.len(), 8);
+    assert_eq!(transfers[0], [0x08, 0b0010_0000, 0x08, 0b0100_0000]);
+    assert_eq!(transfers[1], [0x00, 0x00, 0x07, 0x00]);
+}
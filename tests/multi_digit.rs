@@ -0,0 +1,63 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+mod common;
+
+use eight_segment::{DigitDisplay, Error, MultiDigit, Segments};
+
+use common::{lit, mock_display, pins, MockPin};
+
+#[test]
+fn refresh_shows_one_digit_at_a_time() {
+    let segment_levels = pins(false);
+    let select_levels = pins::<3>(true);
+    let selects = [
+        MockPin(&select_levels[0]),
+        MockPin(&select_levels[1]),
+        MockPin(&select_levels[2]),
+    ];
+    let mut display = MultiDigit::new(mock_display(&segment_levels, true), selects, false);
+    display.display(0, 0x1, false).unwrap();
+    display.display_char(1, 'H', true).unwrap();
+
+    let selected = |levels: &[std::cell::Cell<bool>; 3]| -> Vec<bool> {
+        levels.iter().map(|level| !level.get()).collect()
+    };
+
+    display.refresh().unwrap();
+    assert_eq!(selected(&select_levels), [true, false, false]);
+    assert_eq!(lit(&segment_levels, true), Segments::B | Segments::C);
+
+    display.refresh().unwrap();
+    assert_eq!(selected(&select_levels), [false, true, false]);
+    assert_eq!(
+        lit(&segment_levels, true),
+        Segments::B | Segments::C | Segments::E | Segments::F | Segments::G | Segments::P
+    );
+
+    display.refresh().unwrap();
+    assert_eq!(selected(&select_levels), [false, false, true]);
+    assert_eq!(lit(&segment_levels, true), Segments::NONE);
+
+    display.refresh().unwrap();
+    assert_eq!(selected(&select_levels), [true, false, false]);
+}
+
+#[test]
+fn positions_past_the_last_digit_are_rejected() {
+    let segment_levels = pins(false);
+    let select_levels = pins::<2>(false);
+    let selects = [MockPin(&select_levels[0]), MockPin(&select_levels[1])];
+    let mut display = MultiDigit::new(mock_display(&segment_levels, true), selects, true);
+
+    assert_eq!(
+        display.write_digit(2, Segments::A),
+        Err(Error::InvalidPosition(2))
+    );
+    assert_eq!(
+        display.write_digits(&[Segments::A, Segments::B, Segments::C]),
+        Err(Error::InvalidPosition(2))
+    );
+    display.write_digits(&[Segments::A, Segments::B]).unwrap();
+    assert_eq!(display.frame(), &[Segments::A, Segments::B]);
+}
@@ -0,0 +1,56 @@
+extern crate eight_segment;
+
+use std::convert::Infallible;
+
+use eight_segment::{EightSegment, OutputPort, PortPins, Segments};
+
+/// Records each masked write.
+#[derive(Default)]
+struct MockPort(Vec<(u32, u32)>);
+
+impl OutputPort for MockPort {
+    type Error = Infallible;
+
+    fn write_masked(&mut self, mask: u32, levels: u32) -> Result<(), Infallible> {
+        self.0.push((mask, levels));
+        Ok(())
+    }
+}
+
+#[test]
+fn segments_change_in_one_write() {
+    let pins = PortPins::new(MockPort::default(), [0, 1, 2, 3, 4, 5, 6, 20]);
+    let mut display = EightSegment::new(pins, false);
+    display.display(0x1, true).unwrap();
+    display.write(Segments::ALL).unwrap();
+    assert_eq!(
+        display.release().release().0,
+        [(0x10_007F, 0b0111_1001), (0b0111_1001, 0)]
+    );
+}
+
+#[test]
+fn only_changed_pins_are_written() {
+    let pins = PortPins::new(MockPort::default(), [0, 1, 2, 3, 4, 5, 6, 7]);
+    let mut display = EightSegment::new(pins, true);
+    display.display(0x8, false).unwrap();
+    display.display(0x8, false).unwrap();
+    display.display(0x8, true).unwrap();
+    display.display(0x0, true).unwrap();
+    assert_eq!(display.current(), Segments::from(0xBF));
+
+    display.force_refresh().unwrap();
+    display.high_on = false;
+    display.display(0x0, true).unwrap();
+
+    assert_eq!(
+        display.release().release().0,
+        [
+            (0xFF, 0x7F),
+            (0x80, 0x80),
+            (0x40, 0x00),
+            (0xFF, 0xBF),
+            (0xFF, 0x40),
+        ]
+    );
+}
@@ -0,0 +1,157 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+mod common;
+
+use std::convert::Infallible;
+
+use eight_segment::{EightSegment, Error, Segments};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::ErrorKind;
+use embedded_hal::pwm::{self, ErrorType, SetDutyCycle};
+
+use common::{lit, mock_display, pins, BrokenPin};
+
+/// A PWM channel that records every duty cycle written to it.
+struct MockPwm(Vec<u16>);
+
+/// A PWM channel whose writes always fail.
+struct BrokenPwm;
+
+impl ErrorType for MockPwm {
+    type Error = Infallible;
+}
+
+impl ErrorType for BrokenPwm {
+    type Error = pwm::ErrorKind;
+}
+
+impl SetDutyCycle for BrokenPwm {
+    fn max_duty_cycle(&self) -> u16 {
+        1000
+    }
+
+    fn set_duty_cycle(&mut self, _duty: u16) -> Result<(), pwm::ErrorKind> {
+        Err(pwm::ErrorKind::Other)
+    }
+}
+
+impl SetDutyCycle for MockPwm {
+    fn max_duty_cycle(&self) -> u16 {
+        1000
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Infallible> {
+        self.0.push(duty);
+        Ok(())
+    }
+}
+
+/// A delay that adds up how long it was asked to wait.
+struct MockDelay(u64);
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0 += u64::from(ns);
+    }
+}
+
+#[test]
+fn pwm_level_is_gamma_corrected() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true)
+        .with_pwm(MockPwm(Vec::new()), false)
+        .unwrap();
+    display.display(0x8, false).unwrap();
+    assert_eq!(display.pwm_level(), None);
+
+    display.set_pwm_level(255).unwrap();
+    display.set_pwm_level(128).unwrap();
+    display.set_pwm_level(0).unwrap();
+    assert_eq!(display.pwm_level(), Some(0));
+    assert_eq!(lit(&levels, true), display.current());
+
+    let (_, pwm) = display.release();
+    assert_eq!(pwm.0, [1000, 251, 0]);
+}
+
+#[test]
+fn with_pwm_relights_segments_dimmed_by_software() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true);
+    display.display(0x1, false).unwrap();
+    display.set_brightness(0).unwrap();
+    assert_eq!(lit(&levels, true), Segments::NONE);
+
+    let display = display.with_pwm(MockPwm(Vec::new()), false).unwrap();
+    assert_eq!(lit(&levels, true), Segments::B | Segments::C);
+    assert!(display.release().1 .0.is_empty());
+}
+
+#[test]
+fn with_pwm_hands_everything_back_on_error() {
+    let mut display = EightSegment::new(
+        [
+            BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin, BrokenPin,
+        ],
+        true,
+    );
+    let _ = display.set_brightness(3);
+
+    let error = match display.with_pwm(MockPwm(vec![7]), false) {
+        Ok(_) => panic!("the pins can't be driven"),
+        Err(error) => error,
+    };
+    assert_eq!(error.error, Error::Pin(ErrorKind::Other));
+    assert_eq!(error.display.brightness(), 3);
+    assert_eq!(error.pwm.0, [7]);
+}
+
+#[test]
+fn inverted_output_is_lit_while_low() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true)
+        .with_pwm(MockPwm(Vec::new()), true)
+        .unwrap();
+    display.set_pwm_level(255).unwrap();
+    display.set_pwm_level(0).unwrap();
+    assert_eq!(display.release().1 .0, [0, 1000]);
+}
+
+#[test]
+fn fades_step_through_every_level() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true)
+        .with_pwm(MockPwm(Vec::new()), false)
+        .unwrap();
+    let mut delay = MockDelay(0);
+
+    // Nothing to fade from until the level is known.
+    display.fade_to(255, 1000, &mut delay).unwrap();
+    assert_eq!(delay.0, 0);
+
+    display.fade_out(255, &mut delay).unwrap();
+    assert_eq!(display.pwm_level(), Some(0));
+    assert_eq!(delay.0, 255_000_000);
+
+    display.fade_to(10, 100, &mut delay).unwrap();
+    assert_eq!(display.pwm_level(), Some(10));
+
+    let (_, pwm) = display.release();
+    assert_eq!(pwm.0.len(), 1 + 255 + 10);
+    assert!(pwm.0.windows(2).take(255).all(|w| w[0] >= w[1]));
+    assert_eq!(pwm.0[255], 0);
+}
+
+#[test]
+fn duty_cycle_errors_are_pwm_errors() {
+    let levels = pins(false);
+    let mut display = mock_display(&levels, true)
+        .with_pwm(BrokenPwm, false)
+        .unwrap();
+    assert_eq!(
+        display.set_pwm_level(9),
+        Err(Error::Pwm(pwm::ErrorKind::Other))
+    );
+    assert_eq!(display.pwm_level(), None);
+}
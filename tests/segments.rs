@@ -0,0 +1,50 @@
+extern crate eight_segment;
+
+use eight_segment::{Segment, Segments};
+
+#[test]
+fn iter_yields_lit_segments_in_order() {
+    let seven = Segments::hex(0x7).unwrap() | Segments::P;
+    assert_eq!(
+        seven.iter().collect::<Vec<_>>(),
+        [Segment::A, Segment::B, Segment::C, Segment::P]
+    );
+    assert_eq!(Segments::NONE.iter().next(), None);
+    assert_eq!(Segments::ALL.into_iter().collect::<Vec<_>>(), Segment::ALL);
+    for &segment in &Segment::ALL {
+        let only = Segments::from(segment);
+        assert_eq!(only.iter().collect::<Vec<_>>(), [segment]);
+    }
+}
+
+#[test]
+fn xor_and_not_flip_segments() {
+    let one = Segments::B | Segments::C;
+    assert_eq!(one ^ Segments::C, Segments::B);
+    assert_eq!(one ^ Segments::P, one | Segments::P);
+    assert_eq!(one ^ one, Segments::NONE);
+
+    let mut toggled = one;
+    toggled ^= Segments::ALL;
+    assert_eq!(toggled, !one);
+    assert_eq!(u8::from(!one), !0x06);
+    assert_eq!(!Segments::NONE, Segments::ALL);
+    assert_eq!(!!one, one);
+}
+
+#[test]
+fn hex_patterns_round_trip_through_u8() {
+    let patterns: Vec<Segments> = (0..16).map(|d| Segments::hex(d).unwrap()).collect();
+    for (digit, &segments) in patterns.iter().enumerate() {
+        assert_eq!(Segments::from(u8::from(segments)), segments);
+        assert_eq!(Segments::from_bits(segments.bits()), segments);
+        assert!(!segments.contains(Segments::P), "{:X}", digit);
+        // Every digit is distinguishable, so the pattern identifies the digit.
+        assert_eq!(
+            patterns.iter().position(|&other| other == segments),
+            Some(digit)
+        );
+    }
+    assert_eq!(u8::from(patterns[0]), 0x3F);
+    assert_eq!(Segments::hex(0x10), None);
+}
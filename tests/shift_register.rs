@@ -0,0 +1,90 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+use std::cell::Cell;
+use std::convert::Infallible;
+
+use eight_segment::{
+    DigitDisplay, EightSegment, SegmentMap, Segments, ShiftRegister, ShiftRegisterChain,
+};
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+/// A chain of 74HC595s. Bit 0 of `outputs` is Q0 of the first register, bit 8 is Q0 of the next.
+#[derive(Default)]
+struct Chain {
+    data: Cell<bool>,
+    shift: Cell<u64>,
+    outputs: Cell<u64>,
+}
+
+enum Role {
+    Data,
+    Clock,
+    Latch,
+}
+
+struct ChainPin<'a>(&'a Chain, Role);
+
+impl<'a> ErrorType for ChainPin<'a> {
+    type Error = Infallible;
+}
+
+impl<'a> OutputPin for ChainPin<'a> {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        if let Role::Data = self.1 {
+            self.0.data.set(false);
+        }
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        let chain = self.0;
+        match self.1 {
+            Role::Data => chain.data.set(true),
+            Role::Clock => chain
+                .shift
+                .set(chain.shift.get() << 1 | chain.data.get() as u64),
+            Role::Latch => chain.outputs.set(chain.shift.get()),
+        }
+        Ok(())
+    }
+}
+
+fn pins(chain: &Chain) -> (ChainPin<'_>, ChainPin<'_>, ChainPin<'_>) {
+    (
+        ChainPin(chain, Role::Data),
+        ChainPin(chain, Role::Clock),
+        ChainPin(chain, Role::Latch),
+    )
+}
+
+#[test]
+fn single_register_maps_segments_to_outputs() {
+    let chain = Chain::default();
+    let (data, clock, latch) = pins(&chain);
+    let map = SegmentMap::new([7, 6, 5, 4, 3, 2, 1, 0]);
+    let mut display = EightSegment::new(ShiftRegister::new(data, clock, latch, map), true);
+
+    display.display(0x1, true).unwrap();
+    assert_eq!(chain.outputs.get() & 0xFF, 0b0110_0001);
+
+    display.high_on = false;
+    display.write(Segments::A).unwrap();
+    assert_eq!(chain.outputs.get() & 0xFF, 0b0111_1111);
+}
+
+#[test]
+fn chain_puts_position_zero_nearest_the_mcu() {
+    let chain = Chain::default();
+    let (data, clock, latch) = pins(&chain);
+    let mut display: ShiftRegisterChain<_, _, _, 3> =
+        ShiftRegisterChain::new(data, clock, latch, SegmentMap::IDENTITY, true);
+
+    display
+        .write_digits(&[Segments::A, Segments::B, Segments::P])
+        .unwrap();
+    assert_eq!(chain.outputs.get() & 0xFF_FFFF, 0x80_02_01);
+
+    display.display(1, 0x8, false).unwrap();
+    assert_eq!(chain.outputs.get() & 0xFF_FFFF, 0x80_7F_01);
+}
@@ -0,0 +1,170 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+use std::cell::RefCell;
+use std::convert::Infallible;
+
+use eight_segment::tm1637::{AddressMode, Tm1637};
+use eight_segment::{DigitDisplay, Error, Segments};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+/// Decodes the two-wire protocol into the bytes sent between each start and stop condition.
+struct Bus {
+    clock: bool,
+    dio: bool,
+    acknowledge: bool,
+    bits: Vec<bool>,
+    commands: Vec<Vec<u8>>,
+}
+
+impl Bus {
+    fn new(acknowledge: bool) -> RefCell<Bus> {
+        RefCell::new(Bus {
+            clock: true,
+            dio: true,
+            acknowledge,
+            bits: Vec::new(),
+            commands: Vec::new(),
+        })
+    }
+
+    fn set_clock(&mut self, high: bool) {
+        if high && !self.clock {
+            self.bits.push(self.dio);
+        }
+        self.clock = high;
+    }
+
+    fn set_dio(&mut self, high: bool) {
+        if self.clock && self.dio && !high {
+            self.bits.clear();
+        } else if self.clock && !self.dio && high {
+            // Every ninth bit is the acknowledge clock, and the stop condition adds one more.
+            let bytes = self
+                .bits
+                .chunks_exact(9)
+                .map(|bits| (0..8).fold(0, |byte, i| byte | (bits[i] as u8) << i))
+                .collect();
+            self.commands.push(bytes);
+        }
+        self.dio = high;
+    }
+}
+
+struct Clock<'a>(&'a RefCell<Bus>);
+struct Dio<'a>(&'a RefCell<Bus>);
+struct NoDelay;
+
+impl<'a> ErrorType for Clock<'a> {
+    type Error = Infallible;
+}
+
+impl<'a> OutputPin for Clock<'a> {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().set_clock(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().set_clock(true);
+        Ok(())
+    }
+}
+
+impl<'a> ErrorType for Dio<'a> {
+    type Error = Infallible;
+}
+
+impl<'a> OutputPin for Dio<'a> {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().set_dio(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().set_dio(true);
+        Ok(())
+    }
+}
+
+impl<'a> InputPin for Dio<'a> {
+    fn is_high(&mut self) -> Result<bool, Infallible> {
+        let bus = self.0.borrow();
+        Ok(bus.dio && !bus.acknowledge)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Infallible> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[test]
+fn auto_increment_writes_digits_in_one_command() {
+    let bus = Bus::new(true);
+    let mut display: Tm1637<_, _, _, 4> = Tm1637::new(Clock(&bus), Dio(&bus), NoDelay);
+    display.init().unwrap();
+    display.set_brightness(3).unwrap();
+    display.display(2, 0x2, false).unwrap();
+    display.set_colon(true).unwrap();
+
+    assert_eq!(
+        bus.into_inner().commands,
+        [
+            vec![0x40],
+            vec![0xC0, 0x00, 0x00, 0x00, 0x00],
+            vec![0x8F],
+            vec![0x8B],
+            vec![0x40],
+            vec![0xC2, 0x5B],
+            vec![0x40],
+            vec![0xC1, 0x80],
+        ]
+    );
+}
+
+#[test]
+fn fixed_address_sends_each_digit_separately() {
+    let bus = Bus::new(true);
+    let mut display: Tm1637<_, _, _, 4> = Tm1637::new(Clock(&bus), Dio(&bus), NoDelay);
+    display.address_mode = AddressMode::Fixed;
+    display.write_digits(&[Segments::A, Segments::B]).unwrap();
+    display.set_display_on(false).unwrap();
+
+    assert_eq!(
+        bus.into_inner().commands,
+        [vec![0x44], vec![0xC0, 0x01], vec![0xC1, 0x02], vec![0x87]]
+    );
+}
+
+#[test]
+fn missing_acknowledge_is_an_error() {
+    let bus = Bus::new(false);
+    let mut display: Tm1637<_, _, _, 4> = Tm1637::new(Clock(&bus), Dio(&bus), NoDelay);
+    assert_eq!(display.set_brightness(1), Err(Error::NoAcknowledge));
+}
+
+#[test]
+fn command_after_a_missing_acknowledge_is_framed() {
+    let bus = Bus::new(false);
+    let mut display: Tm1637<_, _, _, 4> = Tm1637::new(Clock(&bus), Dio(&bus), NoDelay);
+    assert_eq!(display.set_brightness(1), Err(Error::NoAcknowledge));
+
+    bus.borrow_mut().acknowledge = true;
+    display.set_brightness(3).unwrap();
+    assert_eq!(bus.into_inner().commands, [vec![0x89], vec![0x8B]]);
+}
+
+#[test]
+fn init_resets_brightness_and_turns_the_display_on() {
+    let bus = Bus::new(true);
+    let mut display: Tm1637<_, _, _, 4> = Tm1637::new(Clock(&bus), Dio(&bus), NoDelay);
+    display.set_brightness(2).unwrap();
+    display.set_display_on(false).unwrap();
+    display.init().unwrap();
+    assert_eq!(bus.into_inner().commands.last(), Some(&vec![0x8F]));
+}
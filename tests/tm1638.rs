@@ -0,0 +1,193 @@
+extern crate eight_segment;
+extern crate embedded_hal;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use eight_segment::tm1638::Tm1638;
+use eight_segment::{DigitDisplay, Error, Segments};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorKind, ErrorType, InputPin, OutputPin};
+
+/// Decodes the bytes sent while STB is low, and plays back key data when it is read. While
+/// `clock_fails` is set, every write to CLK fails.
+#[derive(Default)]
+struct Bus {
+    strobe: bool,
+    clock: bool,
+    dio: bool,
+    bits: Vec<bool>,
+    commands: Vec<Vec<u8>>,
+    keys: VecDeque<bool>,
+    clock_fails: bool,
+}
+
+impl Bus {
+    fn with_keys(bytes: [u8; 4]) -> RefCell<Bus> {
+        let keys = bytes
+            .iter()
+            .flat_map(|&byte| (0..8).map(move |bit| byte & 1 << bit != 0))
+            .collect();
+        RefCell::new(Bus {
+            keys,
+            ..Bus::default()
+        })
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.bits
+            .chunks_exact(8)
+            .map(|bits| (0..8).fold(0, |byte, i| byte | (bits[i] as u8) << i))
+            .collect()
+    }
+
+    fn reading(&self) -> bool {
+        self.bytes().first() == Some(&0x42)
+    }
+}
+
+enum Role {
+    Strobe,
+    Clock,
+    Dio,
+}
+
+struct BusPin<'a>(&'a RefCell<Bus>, Role);
+struct NoDelay;
+
+impl<'a> ErrorType for BusPin<'a> {
+    type Error = ErrorKind;
+}
+
+impl<'a> BusPin<'a> {
+    fn set(&mut self, high: bool) -> Result<(), ErrorKind> {
+        let mut bus = self.0.borrow_mut();
+        match self.1 {
+            Role::Strobe => {
+                if high && !bus.strobe {
+                    let bytes = bus.bytes();
+                    bus.commands.push(bytes);
+                }
+                if !high {
+                    bus.bits.clear();
+                }
+                bus.strobe = high;
+            }
+            Role::Clock => {
+                if bus.clock_fails {
+                    return Err(ErrorKind::Other);
+                }
+                if high && !bus.clock && !bus.strobe && !bus.reading() {
+                    let dio = bus.dio;
+                    bus.bits.push(dio);
+                }
+                bus.clock = high;
+            }
+            Role::Dio => bus.dio = high,
+        }
+        Ok(())
+    }
+}
+
+impl<'a> OutputPin for BusPin<'a> {
+    fn set_low(&mut self) -> Result<(), ErrorKind> {
+        self.set(false)
+    }
+
+    fn set_high(&mut self) -> Result<(), ErrorKind> {
+        self.set(true)
+    }
+}
+
+impl<'a> InputPin for BusPin<'a> {
+    fn is_high(&mut self) -> Result<bool, ErrorKind> {
+        Ok(self
+            .0
+            .borrow_mut()
+            .keys
+            .pop_front()
+            .expect("read past the key data"))
+    }
+
+    fn is_low(&mut self) -> Result<bool, ErrorKind> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+fn panel(bus: &RefCell<Bus>) -> Tm1638<BusPin<'_>, BusPin<'_>, BusPin<'_>, NoDelay> {
+    Tm1638::new(
+        BusPin(bus, Role::Strobe),
+        BusPin(bus, Role::Clock),
+        BusPin(bus, Role::Dio),
+        NoDelay,
+    )
+}
+
+#[test]
+fn digits_and_leds_share_display_ram() {
+    let bus = Bus::with_keys([0; 4]);
+    let mut panel = panel(&bus);
+    panel.set_led(2, true).unwrap();
+    panel.display(1, 0x1, true).unwrap();
+    panel.write_digits(&[Segments::A]).unwrap();
+    assert_eq!(panel.set_led(8, true), Err(Error::InvalidPosition(8)));
+
+    let commands = bus.into_inner().commands;
+    assert_eq!(
+        commands[..5],
+        [
+            vec![0x44],
+            vec![0xC5, 0x01],
+            vec![0x44],
+            vec![0xC2, 0x86],
+            vec![0x40]
+        ]
+    );
+    assert_eq!(
+        commands[5],
+        [
+            0xC0, 0x01, 0x00, 0x86, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00
+        ]
+    );
+}
+
+#[test]
+fn keys_are_decoded_from_the_scan_bytes() {
+    // KS1 on K3, KS2 on K1, and KS8 on K2
+    let bus = Bus::with_keys([0b0100_0001, 0, 0, 0b0010_0000]);
+    assert_eq!(panel(&bus).read_keys().unwrap(), 1 << 16 | 1 << 1 | 1 << 15);
+
+    // S1, S6 and S8 on the LED&KEY board
+    let bus = Bus::with_keys([0b0000_0001, 0b0001_0000, 0, 0b0001_0000]);
+    assert_eq!(panel(&bus).read_buttons().unwrap(), 0b1010_0001);
+    assert_eq!(bus.into_inner().commands, [vec![0x42]]);
+}
+
+#[test]
+fn strobe_is_released_after_a_pin_error() {
+    let bus = Bus::with_keys([0; 4]);
+    let mut panel = panel(&bus);
+    bus.borrow_mut().clock_fails = true;
+    assert_eq!(panel.set_brightness(1), Err(Error::Pin(ErrorKind::Other)));
+    assert_eq!(panel.read_keys(), Err(Error::Pin(ErrorKind::Other)));
+    assert!(bus.borrow().strobe);
+
+    bus.borrow_mut().clock_fails = false;
+    panel.set_brightness(2).unwrap();
+    assert_eq!(bus.into_inner().commands.last(), Some(&vec![0x8A]));
+}
+
+#[test]
+fn init_resets_brightness_and_turns_the_display_on() {
+    let bus = Bus::with_keys([0; 4]);
+    let mut panel = panel(&bus);
+    panel.set_brightness(2).unwrap();
+    panel.set_display_on(false).unwrap();
+    panel.init().unwrap();
+    assert_eq!(bus.into_inner().commands.last(), Some(&vec![0x8F]));
+}